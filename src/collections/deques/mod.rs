@@ -0,0 +1,3 @@
+pub mod ringdeque;
+
+pub use self::ringdeque::{Overflow, RingDeque, RingDequeIntoIterator, RingDequeIterator, RingDequeIteratorMut};
@@ -0,0 +1,683 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::mem::MaybeUninit;
+use std::ops::Index;
+use std::ptr;
+use std::slice;
+
+// what a bounded deque does with a push that does not fit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Overflow {
+    // the new element is handed straight back and the deque stays as it was
+    Reject,
+
+    // the element at the other end makes room and is handed back instead
+    Overwrite,
+}
+
+// double ended queue in one ring buffer. the elements are the count slots starting at head, wrapping around the
+// end of the buffer. a growable deque doubles its buffer when it is full, a bounded one applies its Overflow policy.
+pub struct RingDeque<T> {
+    buf: Vec<MaybeUninit<T>>, // always exactly capacity slots long, only the count slots from head are initialized
+    head: usize, // slot of the front element
+    count: usize, // the amount of elements in the deque
+    overflow: Option<Overflow> // none for a growable deque
+}
+
+impl<T> RingDeque<T> {
+    pub fn new() -> Self {
+        RingDeque::with_capacity(0)
+    }
+
+    // growable deque that does not reallocate before it holds more than capacity elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: RingDeque::alloc(capacity),
+            head: 0,
+            count: 0,
+            overflow: None
+        }
+    }
+
+    // deque that never holds more than capacity elements and never reallocates
+    pub fn bounded(capacity: usize, overflow: Overflow) -> Self {
+        let mut deque = RingDeque::with_capacity(capacity);
+        deque.overflow = Some(overflow);
+        deque
+    }
+
+    fn alloc(capacity: usize) -> Vec<MaybeUninit<T>> {
+        (0..capacity).map(|_| MaybeUninit::uninit()).collect()
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count == self.capacity()
+    }
+
+    // the overflow policy, none when the deque grows instead
+    pub fn overflow(&self) -> Option<Overflow> {
+        self.overflow
+    }
+
+    // slot of the element at index, index may be one past the last element
+    fn slot(&self, index: usize) -> usize {
+        let slot = self.head + index;
+        if slot >= self.capacity() {
+            slot - self.capacity()
+        } else {
+            slot
+        }
+    }
+
+    // moves the elements to the start of a buffer twice the size
+    fn grow(&mut self) {
+        let capacity = (self.capacity() * 2).max(4);
+        let mut buf = RingDeque::alloc(capacity);
+        for (index, slot) in buf.iter_mut().enumerate().take(self.count) {
+            // SAFETY: the slot holds an element, it is moved out exactly once and the old buffer only holds
+            // MaybeUninit, so dropping it afterwards does not drop the element again
+            *slot = MaybeUninit::new(unsafe { self.buf[self.slot(index)].assume_init_read() });
+        }
+        self.buf = buf;
+        self.head = 0;
+    }
+
+    // makes room for one more element, false when there is no room and the push has to be refused.
+    // evicted is what an overwriting deque pushed out of the end the element is not pushed to
+    fn reserve_one(&mut self, evict_back: bool) -> (bool, Option<T>) {
+        if !self.is_full() {
+            return (true, None);
+        }
+
+        match self.overflow {
+            None => {
+                self.grow();
+                (true, None)
+            },
+            Some(Overflow::Overwrite) if self.capacity() > 0 => {
+                let evicted = if evict_back { self.pop_back() } else { self.pop_front() };
+                (true, evicted)
+            },
+            Some(_) => (false, None),
+        }
+    }
+
+    // adds element to the front. hands back the element that did not fit: element itself when a full deque
+    // rejects it, the back element when a full deque overwrites
+    pub fn push_front(&mut self, element: T) -> Option<T> {
+        let (room, evicted) = self.reserve_one(true);
+        if !room {
+            return Some(element);
+        }
+
+        self.head = self.slot(self.capacity() - 1);
+        self.buf[self.head] = MaybeUninit::new(element);
+        self.count += 1;
+        evicted
+    }
+
+    // adds element to the back. hands back the element that did not fit: element itself when a full deque
+    // rejects it, the front element when a full deque overwrites
+    pub fn push_back(&mut self, element: T) -> Option<T> {
+        let (room, evicted) = self.reserve_one(false);
+        if !room {
+            return Some(element);
+        }
+
+        let slot = self.slot(self.count);
+        self.buf[slot] = MaybeUninit::new(element);
+        self.count += 1;
+        evicted
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        // SAFETY: the front slot holds an element, which stops being part of the deque right after the read
+        let element = unsafe { self.buf[self.head].assume_init_read() };
+        self.head = self.slot(1);
+        self.count -= 1;
+        Some(element)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        self.count -= 1;
+        // SAFETY: the back slot holds an element, which stopped being part of the deque with the count above
+        Some(unsafe { self.buf[self.slot(self.count)].assume_init_read() })
+    }
+
+    // O(1)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.count {
+            return None;
+        }
+
+        // SAFETY: every slot of an index below count holds an element
+        Some(unsafe { self.buf[self.slot(index)].assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.count {
+            return None;
+        }
+
+        let slot = self.slot(index);
+        // SAFETY: every slot of an index below count holds an element
+        Some(unsafe { self.buf[slot].assume_init_mut() })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.count.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    // the element n places after the front becomes the front, O(min(n, size - n)) element moves or O(1) when full
+    pub fn rotate_left(&mut self, n: usize) -> &mut Self {
+        if self.count == 0 {
+            return self;
+        }
+
+        let n = n % self.count;
+        if self.is_full() {
+            self.head = self.slot(n);
+        } else if n <= self.count / 2 {
+            for _ in 0..n {
+                // there is room for the element that was just popped, so this never overflows
+                let element = self.pop_front().unwrap();
+                self.push_back(element);
+            }
+        } else {
+            for _ in 0..self.count - n {
+                let element = self.pop_back().unwrap();
+                self.push_front(element);
+            }
+        }
+        self
+    }
+
+    // the element n places before the front becomes the front
+    pub fn rotate_right(&mut self, n: usize) -> &mut Self {
+        if self.count == 0 {
+            return self;
+        }
+
+        let steps = self.count - n % self.count;
+        self.rotate_left(steps)
+    }
+
+    // the elements in order, as the part up to the end of the buffer and the part that wrapped around
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.ranges();
+        // SAFETY: both ranges cover initialized slots only, and MaybeUninit<T> has the layout of T
+        unsafe {
+            (slice::from_raw_parts(self.buf.as_ptr().add(first.0) as *const T, first.1),
+             slice::from_raw_parts(self.buf.as_ptr() as *const T, second))
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (first, second) = self.ranges();
+        let (wrapped, rest) = self.buf.split_at_mut(first.0);
+        // SAFETY: both ranges cover initialized slots only and do not overlap, and MaybeUninit<T> has the layout of T
+        unsafe {
+            (slice::from_raw_parts_mut(rest.as_mut_ptr() as *mut T, first.1),
+             slice::from_raw_parts_mut(wrapped.as_mut_ptr() as *mut T, second))
+        }
+    }
+
+    // (start, len) of the part from head and len of the wrapped part, which always starts at slot 0
+    fn ranges(&self) -> ((usize, usize), usize) {
+        let first = self.count.min(self.capacity() - self.head);
+        ((self.head, first), self.count - first)
+    }
+
+    // moves the elements so they are in order in one slice, O(capacity) when they wrap around
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head + self.count > self.capacity() {
+            // rotating the whole buffer keeps the ring order, so the front ends up in slot 0
+            self.buf.rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        let (first, second) = self.as_mut_slices();
+        let (first, second) = (first as *mut [T], second as *mut [T]);
+
+        // forget the elements before dropping them, so a panicking drop cannot lead to a double drop
+        self.head = 0;
+        self.count = 0;
+        // SAFETY: the slices cover exactly the initialized slots, which the deque no longer considers elements
+        unsafe {
+            ptr::drop_in_place(first);
+            ptr::drop_in_place(second);
+        }
+        self
+    }
+
+    pub fn iter(&self) -> RingDequeIterator<'_, T> {
+        let (first, second) = self.as_slices();
+        RingDequeIterator {
+            first: first.iter(),
+            second: second.iter()
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> RingDequeIteratorMut<'_, T> {
+        let (first, second) = self.as_mut_slices();
+        RingDequeIteratorMut {
+            first: first.iter_mut(),
+            second: second.iter_mut()
+        }
+    }
+}
+
+impl<T: PartialEq> RingDeque<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+}
+
+impl<T> Drop for RingDeque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for RingDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for RingDeque<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut deque = RingDeque::with_capacity(vec.len());
+        deque.extend(vec);
+        deque
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for RingDeque<T> {
+    fn from(array: [T; N]) -> Self {
+        IntoIterator::into_iter(array).collect()
+    }
+}
+
+impl<T> FromIterator<T> for RingDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = RingDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+// pushes to the back, a bounded deque applies its overflow policy to every element
+impl<T> Extend<T> for RingDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<'a, T: Clone> Extend<&'a T> for RingDeque<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T> IntoIterator for RingDeque<T> {
+    type Item = T;
+    type IntoIter = RingDequeIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RingDequeIntoIterator { deque: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingDeque<T> {
+    type Item = &'a T;
+    type IntoIter = RingDequeIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = RingDequeIteratorMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// the copy has the same capacity and overflow policy
+impl<T: Clone> Clone for RingDeque<T> {
+    fn clone(&self) -> Self {
+        let mut deque = RingDeque::with_capacity(self.capacity());
+        deque.overflow = self.overflow;
+        deque.extend(self.iter());
+        deque
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RingDeque<T> {}
+
+impl<T: PartialOrd> PartialOrd for RingDeque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for RingDeque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for RingDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.count);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for RingDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(element) => element,
+            None => panic!("index out of bounds: the len is {} but the index is {}", self.count, index),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct RingDequeIterator<'a, T> {
+    first: slice::Iter<'a, T>, // the elements up to the end of the buffer
+    second: slice::Iter<'a, T> // the elements that wrapped around to its start
+}
+
+pub struct RingDequeIteratorMut<'a, T> {
+    first: slice::IterMut<'a, T>,
+    second: slice::IterMut<'a, T>
+}
+
+pub struct RingDequeIntoIterator<T> {
+    deque: RingDeque<T>
+}
+
+impl<'a, T> Iterator for RingDequeIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RingDequeIterator<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.second.next_back().or_else(|| self.first.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RingDequeIterator<'a, T> {}
+
+impl<'a, T> Iterator for RingDequeIteratorMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RingDequeIteratorMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.second.next_back().or_else(|| self.first.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RingDequeIteratorMut<'a, T> {}
+
+impl<T> Iterator for RingDequeIntoIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.count, Some(self.deque.count))
+    }
+}
+
+impl<T> DoubleEndedIterator for RingDequeIntoIterator<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for RingDequeIntoIterator<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Overflow, RingDeque};
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    #[test]
+    fn rdeque_new() {
+        let mut deque = RingDeque::new() as RingDeque<i32>;
+        assert!(deque.is_empty());
+        assert_eq!(deque.capacity(), 0);
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.front(), None);
+        assert_eq!(deque.back(), None);
+        assert_eq!(deque.as_slices(), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn rdeque_push_pop_both_ends() {
+        let mut deque = RingDeque::new();
+        for x in 0..10 {
+            assert_eq!(deque.push_back(x), None);
+            assert_eq!(deque.push_front(-x), None);
+        }
+        assert_eq!(deque.size(), 20);
+        assert_eq!(deque.front(), Some(&-9));
+        assert_eq!(deque.back(), Some(&9));
+        assert_eq!(deque[10], 0);
+        assert_eq!(deque.pop_front(), Some(-9));
+        assert_eq!(deque.pop_back(), Some(9));
+        assert_eq!(deque.get(17), Some(&8));
+        assert_eq!(deque.get(18), None);
+    }
+
+    #[test]
+    fn rdeque_matches_vecdeque() {
+        let mut deque = RingDeque::with_capacity(3);
+        let mut model = VecDeque::new();
+        let mut seed = 99u64;
+        for step in 0..2000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            match (seed >> 33) % 6 {
+                0 | 1 => {
+                    deque.push_back(step);
+                    model.push_back(step);
+                },
+                2 => {
+                    deque.push_front(step);
+                    model.push_front(step);
+                },
+                3 => assert_eq!(deque.pop_front(), model.pop_front()),
+                4 => assert_eq!(deque.pop_back(), model.pop_back()),
+                _ => {
+                    let n = (seed >> 40) as usize % (model.len() + 1);
+                    if !model.is_empty() {
+                        deque.rotate_left(n);
+                        model.rotate_left(n % model.len());
+                    }
+                },
+            }
+            assert_eq!(deque.size(), model.len());
+        }
+        assert!(deque.iter().eq(model.iter()));
+        assert!(deque.iter().rev().eq(model.iter().rev()));
+    }
+
+    #[test]
+    fn rdeque_slices_and_make_contiguous() {
+        let mut deque = RingDeque::with_capacity(4);
+        deque.extend(vec![2, 3]);
+        deque.push_front(1);
+        deque.push_front(0);
+        let (first, second) = deque.as_slices();
+        assert_eq!(first.len() + second.len(), 4);
+        assert!(!second.is_empty());
+        assert_eq!(deque.make_contiguous(), &mut [0, 1, 2, 3]);
+        assert_eq!(deque.as_slices(), (&[0, 1, 2, 3][..], &[][..]));
+        deque.as_mut_slices().0[1] = 10;
+        assert_eq!(deque, RingDeque::from([0, 10, 2, 3]));
+    }
+
+    #[test]
+    fn rdeque_rotate() {
+        let mut deque: RingDeque<i32> = (0..5).collect();
+        deque.rotate_left(2);
+        assert_eq!(deque, RingDeque::from([2, 3, 4, 0, 1]));
+        deque.rotate_right(3);
+        assert_eq!(deque, RingDeque::from([4, 0, 1, 2, 3]));
+
+        // a full deque rotates by moving its head only
+        let mut full = RingDeque::bounded(3, Overflow::Reject);
+        full.extend(vec![0, 1, 2]);
+        full.rotate_left(1);
+        assert_eq!(full, RingDeque::from([1, 2, 0]));
+    }
+
+    #[test]
+    fn rdeque_bounded_reject() {
+        let mut deque = RingDeque::bounded(2, Overflow::Reject);
+        assert_eq!(deque.push_back(1), None);
+        assert_eq!(deque.push_back(2), None);
+        assert!(deque.is_full());
+        assert_eq!(deque.push_back(3), Some(3));
+        assert_eq!(deque.push_front(0), Some(0));
+        assert_eq!(deque, RingDeque::from([1, 2]));
+        assert_eq!(deque.capacity(), 2);
+        assert_eq!(deque.overflow(), Some(Overflow::Reject));
+    }
+
+    #[test]
+    fn rdeque_bounded_overwrite() {
+        let mut log = RingDeque::bounded(3, Overflow::Overwrite);
+        for x in 0..5 {
+            log.push_back(x);
+        }
+        assert_eq!(log, RingDeque::from([2, 3, 4]));
+        assert_eq!(log.push_back(5), Some(2));
+        assert_eq!(log.push_front(1), Some(5));
+        assert_eq!(log, RingDeque::from([1, 3, 4]));
+        assert_eq!(log.capacity(), 3);
+
+        let mut zero = RingDeque::bounded(0, Overflow::Overwrite);
+        assert_eq!(zero.push_back(1), Some(1));
+    }
+
+    #[test]
+    fn rdeque_drops_elements() {
+        let tracker = Rc::new(());
+        let mut deque = RingDeque::with_capacity(4);
+        for _ in 0..3 {
+            deque.push_front(Rc::clone(&tracker));
+        }
+        deque.push_back(Rc::clone(&tracker));
+        deque.pop_front();
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        deque.clear();
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        for _ in 0..6 {
+            deque.push_back(Rc::clone(&tracker));
+        }
+        let mut iter = deque.clone().into_iter();
+        iter.next();
+        drop(iter);
+        drop(deque);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn rdeque_traits() {
+        let mut deque = RingDeque::from(vec![String::from("a"), String::from("b")]);
+        deque.extend(&[String::from("c")]);
+        for x in deque.iter_mut() {
+            x.push('!');
+        }
+        assert_eq!(format!("{:?}", deque), r#"["a!", "b!", "c!"]"#);
+        assert_eq!(deque.index(&String::from("b!")), Some(1));
+        assert!(deque.has(&String::from("c!")));
+        assert!(RingDeque::from([1, 2]) < RingDeque::from([1, 3]));
+        assert_eq!(deque.into_iter().rev().collect::<Vec<String>>(), vec!["c!", "b!", "a!"]);
+    }
+}
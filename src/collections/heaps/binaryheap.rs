@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::slice;
+use std::vec;
+
+// priority queue in an implicit binary tree, the children of index i are at 2i + 1 and 2i + 2. the top is the
+// element that compares greatest under the comparator, so new is a max heap and new_min a min heap. the
+// comparator is a plain fn so heaps stay Clone and nameable, closures without captures coerce to it
+pub struct BinaryHeap<T> {
+    data: Vec<T>,
+    compare: fn(&T, &T) -> Ordering
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    // pops the greatest element first
+    pub fn new() -> Self {
+        BinaryHeap::with_comparator(T::cmp)
+    }
+
+    // pops the smallest element first
+    pub fn new_min() -> Self {
+        BinaryHeap::with_comparator(|a, b| b.cmp(a))
+    }
+}
+
+impl<T> BinaryHeap<T> {
+    // pops the element that compares greatest under compare first
+    pub fn with_comparator(compare: fn(&T, &T) -> Ordering) -> Self {
+        Self {
+            data: Vec::new(),
+            compare
+        }
+    }
+
+    // builds the heap bottom up in O(n)
+    pub fn from_vec(data: Vec<T>, compare: fn(&T, &T) -> Ordering) -> Self {
+        let mut heap = Self { data, compare };
+        heap.rebuild();
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    // O(log n)
+    pub fn push(&mut self, element: T) -> &mut Self {
+        self.data.push(element);
+        self.sift_up(self.data.len() - 1);
+        self
+    }
+
+    // O(log n)
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+
+        let top = mem::replace(&mut self.data[0], last);
+        self.sift_down(0, self.data.len());
+        Some(top)
+    }
+
+    // the element pop hands out next
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    // moves every element of other into the heap, other is empty afterwards. O(n + m)
+    pub fn append(&mut self, other: &mut BinaryHeap<T>) -> &mut Self {
+        self.data.append(&mut other.data);
+        self.rebuild();
+        self
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.data.clear();
+        self
+    }
+
+    // in no particular order
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    // takes every element out at once in no particular order, the heap is empty afterwards
+    pub fn drain(&mut self) -> vec::Drain<'_, T> {
+        self.data.drain(..)
+    }
+
+    // the elements in no particular order
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    // ascending under the comparator, the reverse of the order pop hands them out in. O(n log n)
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    fn rebuild(&mut self) {
+        let len = self.data.len();
+        for index in (0..len / 2).rev() {
+            self.sift_down(index, len);
+        }
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if (self.compare)(&self.data[index], &self.data[parent]) != Ordering::Greater {
+                break;
+            }
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    // only the first end elements are part of the heap
+    fn sift_down(&mut self, mut index: usize, end: usize) {
+        loop {
+            let mut child = 2 * index + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && (self.compare)(&self.data[child + 1], &self.data[child]) == Ordering::Greater {
+                child += 1;
+            }
+            if (self.compare)(&self.data[child], &self.data[index]) != Ordering::Greater {
+                break;
+            }
+            self.data.swap(index, child);
+            index = child;
+        }
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// max heap, use from_vec for another ordering
+impl<T: Ord> From<Vec<T>> for BinaryHeap<T> {
+    fn from(vec: Vec<T>) -> Self {
+        BinaryHeap::from_vec(vec, T::cmp)
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for BinaryHeap<T> {
+    fn from(array: [T; N]) -> Self {
+        BinaryHeap::from(Vec::from(array))
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BinaryHeap::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> Extend<T> for BinaryHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+// in no particular order
+impl<T> IntoIterator for BinaryHeap<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BinaryHeap<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> Clone for BinaryHeap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            compare: self.compare
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryHeap;
+
+    #[test]
+    fn bheap_push_pop() {
+        let mut heap = BinaryHeap::new();
+        assert_eq!(heap.pop(), None);
+        heap.push(3).push(1).push(4).push(1).push(5);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some(&5));
+        let mut popped = Vec::new();
+        while let Some(x) = heap.pop() {
+            popped.push(x);
+        }
+        assert_eq!(popped, vec![5, 4, 3, 1, 1]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn bheap_min_and_comparator() {
+        let mut heap: BinaryHeap<i32> = BinaryHeap::new_min();
+        heap.extend(vec![7, -2, 9, 0]);
+        assert_eq!(heap.pop(), Some(-2));
+        assert_eq!(heap.pop(), Some(0));
+
+        let mut by_len = BinaryHeap::with_comparator(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+        by_len.extend(vec!["ccc", "a", "bb"]);
+        assert_eq!(by_len.pop(), Some("ccc"));
+        assert_eq!(by_len.into_sorted_vec(), vec!["a", "bb"]);
+    }
+
+    #[test]
+    fn bheap_heapify_and_sorted_vec() {
+        let mut seed = 7u64;
+        let mut values = Vec::new();
+        for _ in 0..300 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            values.push((seed >> 40) as i32 % 100);
+        }
+        let heap = BinaryHeap::from(values.clone());
+        values.sort();
+        assert_eq!(heap.clone().into_sorted_vec(), values);
+        assert_eq!(BinaryHeap::from_vec(values.clone(), |a, b| b.cmp(a)).pop(), values.first().copied());
+        assert_eq!(heap.into_iter().count(), 300);
+    }
+
+    #[test]
+    fn bheap_append_drain() {
+        let mut a = BinaryHeap::from([1, 5]);
+        let mut b = BinaryHeap::from([3, 8]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(a.peek(), Some(&8));
+        let mut drained: Vec<i32> = a.drain().collect();
+        drained.sort();
+        assert_eq!(drained, vec![1, 3, 5, 8]);
+        assert!(a.is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+use crate::collections::heaps::mergeableheap::HeapId;
+use crate::collections::heaps::MergeableHeap;
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::rc::{Rc, Weak};
+
+type BinomialNodePtr<T> = Rc<RefCell<BinomialNode<T>>>;
+type WeakBinomialNodePtr<T> = Weak<RefCell<BinomialNode<T>>>;
+// where an element currently lives, decrease_key moves elements between nodes and updates it
+type Locator<T> = Rc<RefCell<WeakBinomialNodePtr<T>>>;
+
+struct BinomialNode<T> {
+    element: T,
+    locator: Locator<T>, // travels with the element, the handles point to it
+    heap: Rc<HeapId>, // the heap the node was pushed to, see HeapId
+    parent: WeakBinomialNodePtr<T>, // dangling for a root
+    children: Vec<BinomialNodePtr<T>> // children[i] is the root of a tree of order i
+}
+
+// entry of a BinomialHeap, goes stale once the entry is popped
+pub struct BinomialHandle<T> {
+    locator: Weak<RefCell<WeakBinomialNodePtr<T>>>
+}
+
+// a forest of binomial trees with at most one tree of every order, like the bits of the element count. meld adds
+// the forests like binary numbers and pop_min melds the children of the smallest root back in, both O(log n).
+// decrease_key swaps the element up towards the root, O(log n)
+pub struct BinomialHeap<T> {
+    roots: Vec<Option<BinomialNodePtr<T>>>, // roots[i] is the tree of order i, if there is one
+    count: usize,
+    id: Rc<HeapId>
+}
+
+impl<T> BinomialHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            roots: Vec::new(),
+            count: 0,
+            id: HeapId::new()
+        }
+    }
+
+    // the trees are only O(log n) deep, so dropping them recursively is fine
+    pub fn clear(&mut self) -> &mut Self {
+        self.roots.clear();
+        self.count = 0;
+        self
+    }
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    // makes the root with the greater element the child of the other, both trees must have the same order
+    fn link(a: BinomialNodePtr<T>, b: BinomialNodePtr<T>) -> BinomialNodePtr<T> {
+        let (parent, child) = if b.borrow().element < a.borrow().element { (b, a) } else { (a, b) };
+        child.borrow_mut().parent = Rc::downgrade(&parent);
+        parent.borrow_mut().children.push(child);
+        parent
+    }
+
+    // adds the trees to the forest with a carry, the way binary numbers are added
+    fn merge(&mut self, trees: Vec<Option<BinomialNodePtr<T>>>) {
+        if trees.len() > self.roots.len() {
+            self.roots.resize(trees.len(), None);
+        }
+
+        let mut carry = None;
+        let mut trees = trees.into_iter();
+        let mut order = 0;
+        loop {
+            let other = trees.next();
+            if other.is_none() && carry.is_none() {
+                break;
+            }
+            if order == self.roots.len() {
+                self.roots.push(None);
+            }
+
+            let trees_here = [self.roots[order].take(), other.flatten(), carry.take()];
+            let mut present: Vec<BinomialNodePtr<T>> = IntoIterator::into_iter(trees_here).flatten().collect();
+            if present.len() >= 2 {
+                let b = present.pop().unwrap();
+                let a = present.pop().unwrap();
+                carry = Some(BinomialHeap::link(a, b));
+            }
+            self.roots[order] = present.pop();
+            order += 1;
+        }
+    }
+
+    fn min_order(&self) -> Option<usize> {
+        self.roots.iter()
+            .enumerate()
+            .filter_map(|(order, root)| root.as_ref().map(|root| (order, root)))
+            .min_by(|(_, a), (_, b)| a.borrow().element.cmp(&b.borrow().element))
+            .map(|(order, _)| order)
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for BinomialHeap<T> {
+    type Handle = BinomialHandle<T>;
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    // O(1) amortized, O(log n) when the carry runs through every order
+    fn push(&mut self, element: T) -> Self::Handle {
+        let locator: Locator<T> = Rc::new(RefCell::new(Weak::new()));
+        let handle = BinomialHandle { locator: Rc::downgrade(&locator) };
+        let node = Rc::new(RefCell::new(BinomialNode {
+            element,
+            locator: Rc::clone(&locator),
+            heap: Rc::clone(&self.id),
+            parent: Weak::new(),
+            children: Vec::new()
+        }));
+        *locator.borrow_mut() = Rc::downgrade(&node);
+
+        self.merge(vec![Some(node)]);
+        self.count += 1;
+        handle
+    }
+
+    // O(log n), the smallest element is at one of the roots
+    fn peek_min(&self) -> Option<&T> {
+        let root = self.roots[self.min_order()?].as_ref().unwrap();
+        // SAFETY: the roots are owned by the heap, which stays borrowed while the reference lives. decrease_key
+        // swaps elements between nodes, but like every other method that borrows a node mutably it takes &mut self
+        Some(unsafe { &(*root.as_ptr()).element })
+    }
+
+    // O(log n)
+    fn pop_min(&mut self) -> Option<T> {
+        let order = self.min_order()?;
+        let root = self.roots[order].take().unwrap();
+        self.count -= 1;
+
+        let children = mem::take(&mut root.borrow_mut().children);
+        for child in &children {
+            child.borrow_mut().parent = Weak::new();
+        }
+        self.merge(children.into_iter().map(Some).collect());
+        while let Some(None) = self.roots.last() {
+            self.roots.pop();
+        }
+
+        // the handles only hold weak pointers to the locator, which goes away with the node
+        Rc::try_unwrap(root).ok().map(|node| node.into_inner().element)
+    }
+
+    // O(log n)
+    fn meld(&mut self, mut other: Self) -> &mut Self {
+        self.merge(mem::take(&mut other.roots));
+        HeapId::merge(&other.id, &self.id);
+        self.count += other.count;
+        other.count = 0;
+        self
+    }
+
+    // O(log n), the element is swapped with its parent's until the parent is no greater
+    fn try_decrease_key(&mut self, handle: &Self::Handle, element: T) -> Result<&mut Self> {
+        let mut node = handle.locator.upgrade()
+            .and_then(|locator| locator.borrow().upgrade())
+            .ok_or(Error::NotFound)?;
+        if !HeapId::owns(&self.id, &mut node.borrow_mut().heap) {
+            return Err(Error::NotFound);
+        }
+        if element > node.borrow().element {
+            return Err(Error::KeyIncreased);
+        }
+        node.borrow_mut().element = element;
+
+        loop {
+            let parent = match node.borrow().parent.upgrade() {
+                Some(parent) => parent,
+                None => break,
+            };
+            if node.borrow().element >= parent.borrow().element {
+                break;
+            }
+
+            {
+                let mut this = node.borrow_mut();
+                let mut up = parent.borrow_mut();
+                mem::swap(&mut this.element, &mut up.element);
+                mem::swap(&mut this.locator, &mut up.locator);
+                *this.locator.borrow_mut() = Rc::downgrade(&node);
+                *up.locator.borrow_mut() = Rc::downgrade(&parent);
+            }
+            node = parent;
+        }
+        Ok(self)
+    }
+}
+
+impl<T> Clone for BinomialHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            locator: Weak::clone(&self.locator)
+        }
+    }
+}
+
+impl<T> fmt::Debug for BinomialHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BinomialHandle").field("live", &(self.locator.strong_count() > 0)).finish()
+    }
+}
+
+impl<T> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = BinomialHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for BinomialHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for BinomialHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BinomialHeap").field("len", &self.count).field("min", &self.peek_min()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinomialHeap;
+    use crate::collections::heaps::MergeableHeap;
+    use crate::error::Error;
+
+    #[test]
+    fn biheap_push_pop() {
+        let mut heap = BinomialHeap::new();
+        assert_eq!(heap.pop_min(), None);
+        assert!(heap.peek_min().is_none());
+        heap.extend(vec![5, 3, 8, 1, 9, 2, 2]);
+        assert_eq!(heap.len(), 7);
+        // 7 elements are trees of order 0, 1 and 2
+        assert_eq!(heap.roots.iter().filter(|root| root.is_some()).count(), 3);
+        assert_eq!(*heap.peek_min().unwrap(), 1);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn biheap_meld() {
+        let mut a: BinomialHeap<i32> = (0..5).map(|x| x * 2).collect();
+        let mut b = BinomialHeap::new();
+        let handle = b.push(7);
+        b.extend(vec![1, 3]);
+        a.meld(b);
+        assert_eq!(a.len(), 8);
+        assert_eq!(a.roots.iter().filter(|root| root.is_some()).count(), 1);
+        a.decrease_key(&handle, -1);
+        assert_eq!(a.into_sorted_vec(), vec![-1, 0, 1, 2, 3, 4, 6, 8]);
+    }
+
+    #[test]
+    fn biheap_decrease_key_follows_swaps() {
+        let mut heap = BinomialHeap::new();
+        let handles: Vec<_> = (0..32).map(|x| heap.push(x)).collect();
+        // bubbling 31 to the top moves the elements on its path down, their handles must follow them
+        heap.decrease_key(&handles[31], -1);
+        heap.decrease_key(&handles[30], -2);
+        heap.decrease_key(&handles[0], -3);
+        assert_eq!(heap.try_decrease_key(&handles[5], 6).err(), Some(Error::KeyIncreased));
+        assert_eq!(heap.pop_min(), Some(-3));
+        assert_eq!(heap.try_decrease_key(&handles[0], -9).err(), Some(Error::NotFound));
+        heap.decrease_key(&handles[16], -4);
+        let mut expected: Vec<i32> = (1..30).filter(|&x| x != 16).chain(vec![-1, -2, -4]).collect();
+        expected.sort();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::collections::heaps::mergeableheap::HeapId;
+use crate::collections::heaps::MergeableHeap;
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::{Rc, Weak};
+
+type FibonacciNodePtr<T> = Rc<RefCell<FibonacciNode<T>>>;
+type WeakFibonacciNodePtr<T> = Weak<RefCell<FibonacciNode<T>>>;
+
+struct FibonacciNode<T> {
+    element: T,
+    heap: Rc<HeapId>, // the heap the node was pushed to, see HeapId
+    degree: usize, // the amount of children
+    marked: bool, // lost a child since it became a child itself
+    parent: WeakFibonacciNodePtr<T>, // dangling for a root
+    child: Option<FibonacciNodePtr<T>>, // first child, the others follow through its next links
+    next: Option<FibonacciNodePtr<T>>, // next sibling, or next root
+    prev: WeakFibonacciNodePtr<T> // previous sibling, dangling for the first one
+}
+
+// entry of a FibonacciHeap, goes stale once the entry is popped
+pub struct FibonacciHandle<T> {
+    node: WeakFibonacciNodePtr<T>
+}
+
+// a list of trees where every node is no greater than its children. push and meld only add to the root list and
+// decrease_key cuts the entry loose, cascading to parents that lose a second child, all O(1) amortized. pop_min
+// links roots of equal degree until every degree is unique, O(log n) amortized. the sibling lists are doubly
+// linked with weak back links, strong links only point down and to the right so no cycle keeps nodes alive
+pub struct FibonacciHeap<T> {
+    roots: Option<FibonacciNodePtr<T>>, // first root
+    last: WeakFibonacciNodePtr<T>, // last root, for melding in O(1)
+    min: Option<FibonacciNodePtr<T>>, // the root holding the smallest element
+    count: usize,
+    id: Rc<HeapId>
+}
+
+impl<T> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            roots: None,
+            last: Weak::new(),
+            min: None,
+            count: 0,
+            id: HeapId::new()
+        }
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        // the root and sibling lists can be as long as the heap, so the links are taken apart iteratively
+        let mut stack: Vec<FibonacciNodePtr<T>> = self.roots.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.next.take());
+        }
+        self.last = Weak::new();
+        self.min = None;
+        self.count = 0;
+        self
+    }
+
+    // adds a detached node to the front of the root list
+    fn push_root(&mut self, node: FibonacciNodePtr<T>) {
+        {
+            let mut root = node.borrow_mut();
+            root.parent = Weak::new();
+            root.prev = Weak::new();
+            root.marked = false;
+            root.next = self.roots.take();
+            match &root.next {
+                Some(next) => next.borrow_mut().prev = Rc::downgrade(&node),
+                None => self.last = Rc::downgrade(&node),
+            }
+        }
+        self.roots = Some(node);
+    }
+
+    // takes every node of a sibling list apart, starting with first
+    fn detach_all(mut first: Option<FibonacciNodePtr<T>>, nodes: &mut Vec<FibonacciNodePtr<T>>) {
+        while let Some(node) = first {
+            {
+                let mut this = node.borrow_mut();
+                first = this.next.take();
+                this.prev = Weak::new();
+                this.parent = Weak::new();
+            }
+            nodes.push(node);
+        }
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    fn is_less(a: &FibonacciNodePtr<T>, b: &FibonacciNodePtr<T>) -> bool {
+        a.borrow().element < b.borrow().element
+    }
+
+    // makes the detached root with the greater element the first child of the other
+    fn link(a: FibonacciNodePtr<T>, b: FibonacciNodePtr<T>) -> FibonacciNodePtr<T> {
+        let (parent, child) = if FibonacciHeap::is_less(&b, &a) { (b, a) } else { (a, b) };
+        {
+            let mut this = parent.borrow_mut();
+            let mut node = child.borrow_mut();
+            node.parent = Rc::downgrade(&parent);
+            node.marked = false;
+            node.next = this.child.take();
+            if let Some(next) = &node.next {
+                next.borrow_mut().prev = Rc::downgrade(&child);
+            }
+            this.degree += 1;
+        }
+        parent.borrow_mut().child = Some(child);
+        parent
+    }
+
+    // moves node from the children of parent to the root list
+    fn cut(&mut self, node: &FibonacciNodePtr<T>, parent: &FibonacciNodePtr<T>) {
+        {
+            let mut this = node.borrow_mut();
+            let next = this.next.take();
+            if let Some(next) = &next {
+                next.borrow_mut().prev = Weak::clone(&this.prev);
+            }
+            match this.prev.upgrade() {
+                Some(prev) => prev.borrow_mut().next = next,
+                None => parent.borrow_mut().child = next,
+            }
+            parent.borrow_mut().degree -= 1;
+        }
+        self.push_root(Rc::clone(node));
+    }
+
+    // cuts parents that already lost a child, up to the first unmarked one which only gets marked
+    fn cascading_cut(&mut self, mut node: FibonacciNodePtr<T>) {
+        loop {
+            let parent = match node.borrow().parent.upgrade() {
+                Some(parent) => parent,
+                None => return,
+            };
+            if !node.borrow().marked {
+                node.borrow_mut().marked = true;
+                return;
+            }
+            self.cut(&node, &parent);
+            node = parent;
+        }
+    }
+
+    // links the trees until every degree is unique and makes them the new root list
+    fn consolidate(&mut self, trees: Vec<FibonacciNodePtr<T>>) {
+        let mut by_degree: Vec<Option<FibonacciNodePtr<T>>> = Vec::new();
+        for mut tree in trees {
+            loop {
+                let degree = tree.borrow().degree;
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(other) => tree = FibonacciHeap::link(tree, other),
+                    None => {
+                        by_degree[degree] = Some(tree);
+                        break;
+                    },
+                }
+            }
+        }
+
+        for tree in by_degree.into_iter().rev().flatten() {
+            if self.min.as_ref().is_none_or(|min| FibonacciHeap::is_less(&tree, min)) {
+                self.min = Some(Rc::clone(&tree));
+            }
+            self.push_root(tree);
+        }
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for FibonacciHeap<T> {
+    type Handle = FibonacciHandle<T>;
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    // O(1)
+    fn push(&mut self, element: T) -> Self::Handle {
+        let node = Rc::new(RefCell::new(FibonacciNode {
+            element,
+            heap: Rc::clone(&self.id),
+            degree: 0,
+            marked: false,
+            parent: Weak::new(),
+            child: None,
+            next: None,
+            prev: Weak::new()
+        }));
+        if self.min.as_ref().is_none_or(|min| FibonacciHeap::is_less(&node, min)) {
+            self.min = Some(Rc::clone(&node));
+        }
+        self.push_root(Rc::clone(&node));
+        self.count += 1;
+        FibonacciHandle { node: Rc::downgrade(&node) }
+    }
+
+    // O(1)
+    fn peek_min(&self) -> Option<&T> {
+        // SAFETY: the min node is in the root list, which the heap owns and which stays borrowed while the
+        // reference lives. the handles only hold weak pointers and nodes are only borrowed mutably under &mut self
+        self.min.as_ref().map(|min| unsafe { &(*min.as_ptr()).element })
+    }
+
+    // O(log n) amortized
+    fn pop_min(&mut self) -> Option<T> {
+        let min = self.min.take()?;
+        self.count -= 1;
+
+        // the other roots and the children of min are consolidated into a new root list
+        let mut trees = Vec::new();
+        FibonacciHeap::detach_all(self.roots.take(), &mut trees);
+        trees.retain(|tree| !Rc::ptr_eq(tree, &min));
+        let children = min.borrow_mut().child.take();
+        FibonacciHeap::detach_all(children, &mut trees);
+        self.last = Weak::new();
+        self.consolidate(trees);
+
+        // the handles only hold weak pointers, so the heap held the last strong one
+        Rc::try_unwrap(min).ok().map(|node| node.into_inner().element)
+    }
+
+    // O(1), the root lists are concatenated
+    fn meld(&mut self, mut other: Self) -> &mut Self {
+        HeapId::merge(&other.id, &self.id);
+        let roots = match other.roots.take() {
+            Some(roots) => roots,
+            None => return self,
+        };
+
+        match self.last.upgrade() {
+            Some(last) => {
+                roots.borrow_mut().prev = Rc::downgrade(&last);
+                last.borrow_mut().next = Some(roots);
+            },
+            None => self.roots = Some(roots),
+        }
+        self.last = Weak::clone(&other.last);
+
+        let other_min = other.min.take();
+        if let Some(min) = other_min {
+            if self.min.as_ref().is_none_or(|current| FibonacciHeap::is_less(&min, current)) {
+                self.min = Some(min);
+            }
+        }
+        self.count += other.count;
+        other.count = 0;
+        self
+    }
+
+    // O(1) amortized
+    fn try_decrease_key(&mut self, handle: &Self::Handle, element: T) -> Result<&mut Self> {
+        let node = handle.node.upgrade().ok_or(Error::NotFound)?;
+        if !HeapId::owns(&self.id, &mut node.borrow_mut().heap) {
+            return Err(Error::NotFound);
+        }
+        if element > node.borrow().element {
+            return Err(Error::KeyIncreased);
+        }
+        node.borrow_mut().element = element;
+
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            if FibonacciHeap::is_less(&node, &parent) {
+                self.cut(&node, &parent);
+                self.cascading_cut(parent);
+            }
+        }
+        if self.min.as_ref().is_some_and(|min| FibonacciHeap::is_less(&node, min)) {
+            self.min = Some(node);
+        }
+        Ok(self)
+    }
+}
+
+impl<T> Clone for FibonacciHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: Weak::clone(&self.node)
+        }
+    }
+}
+
+impl<T> fmt::Debug for FibonacciHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FibonacciHandle").field("live", &(self.node.strong_count() > 0)).finish()
+    }
+}
+
+impl<T> Drop for FibonacciHeap<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for FibonacciHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = FibonacciHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for FibonacciHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for FibonacciHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FibonacciHeap").field("len", &self.count).field("min", &self.peek_min()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FibonacciHeap;
+    use crate::collections::heaps::MergeableHeap;
+    use crate::error::Error;
+
+    #[test]
+    fn fheap_push_pop() {
+        let mut heap = FibonacciHeap::new();
+        assert_eq!(heap.pop_min(), None);
+        heap.extend(vec![5, 3, 8, 1, 9, 2, 2]);
+        assert_eq!(*heap.peek_min().unwrap(), 1);
+        assert_eq!(heap.pop_min(), Some(1));
+        heap.push(0);
+        assert_eq!(heap.into_sorted_vec(), vec![0, 2, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn fheap_meld() {
+        let mut a: FibonacciHeap<i32> = vec![4, 10].into_iter().collect();
+        let mut b = FibonacciHeap::new();
+        let handle = b.push(7);
+        b.push(2);
+        a.meld(b).meld(FibonacciHeap::new());
+        assert_eq!(a.len(), 4);
+        assert_eq!(*a.peek_min().unwrap(), 2);
+        a.decrease_key(&handle, 1);
+        a.push(3);
+        assert_eq!(a.into_sorted_vec(), vec![1, 2, 3, 4, 10]);
+    }
+
+    #[test]
+    fn fheap_cascading_cuts() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..64).map(|x| heap.push(x + 100)).collect();
+        heap.push(0);
+        // consolidating leaves one binomial tree of 64 nodes under the remaining root
+        assert_eq!(heap.pop_min(), Some(0));
+
+        let mut expected: Vec<i32> = (100..164).collect();
+        for (i, handle) in handles.iter().enumerate().rev().step_by(3) {
+            let key = -(i as i32);
+            heap.decrease_key(handle, key);
+            expected[i] = key;
+        }
+        assert_eq!(heap.try_decrease_key(&handles[1], 1000).err(), Some(Error::KeyIncreased));
+        expected.sort();
+        assert_eq!(heap.pop_min(), Some(expected[0]));
+        assert_eq!(heap.try_decrease_key(&handles[63], -1000).err(), Some(Error::NotFound));
+        assert_eq!(heap.into_sorted_vec(), expected[1..].to_vec());
+    }
+
+    #[test]
+    fn fheap_long_root_list_drop() {
+        let mut heap = FibonacciHeap::new();
+        for x in 0..200_000 {
+            heap.push(x);
+        }
+        drop(heap);
+    }
+}
@@ -0,0 +1,410 @@
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+// hands every queue its own id, so a handle is only accepted by the queue that gave it out
+static NEXT_QUEUE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// names an entry of the IndexedPriorityQueue that handed it out. it stays valid until the entry is popped or
+// removed, after which the generation no longer matches and the handle is refused instead of hitting a newer entry.
+// other queues refuse it as well, even where they have a live entry in the same slot
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct QueueHandle {
+    queue: usize, // id of the queue the entry was pushed to
+    slot: usize,
+    generation: usize
+}
+
+struct Slot<K, P> {
+    entry: Option<(K, P)>, // none while the slot is free
+    position: usize, // index of the slot in the heap
+    generation: usize // bumped every time the entry leaves, so old handles go stale
+}
+
+// binary heap of keys ordered by their priority, whose entries can be reached through handles to change their
+// priority or take them out in O(log n). the entries live in slots that never move, the heap only orders slot
+// indices and every slot remembers where in the heap it is. like BinaryHeap the top is the greatest priority
+// under the comparator
+pub struct IndexedPriorityQueue<K, P> {
+    slots: Vec<Slot<K, P>>,
+    free: Vec<usize>, // slots without an entry, reused before the slots grow
+    heap: Vec<usize>,
+    compare: fn(&P, &P) -> Ordering,
+    id: usize // stamped on every handle, see NEXT_QUEUE_ID
+}
+
+impl<K, P: Ord> IndexedPriorityQueue<K, P> {
+    // pops the greatest priority first
+    pub fn new() -> Self {
+        IndexedPriorityQueue::with_comparator(P::cmp)
+    }
+
+    // pops the smallest priority first, as Dijkstra style searches want
+    pub fn new_min() -> Self {
+        IndexedPriorityQueue::with_comparator(|a, b| b.cmp(a))
+    }
+}
+
+impl<K, P> IndexedPriorityQueue<K, P> {
+    // pops the priority that compares greatest under compare first
+    pub fn with_comparator(compare: fn(&P, &P) -> Ordering) -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            heap: Vec::new(),
+            compare,
+            id: NEXT_QUEUE_ID.fetch_add(1, AtomicOrdering::Relaxed)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // O(log n)
+    pub fn push(&mut self, key: K, priority: P) -> QueueHandle {
+        let position = self.heap.len();
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].entry = Some((key, priority));
+                self.slots[slot].position = position;
+                slot
+            },
+            None => {
+                self.slots.push(Slot { entry: Some((key, priority)), position, generation: 0 });
+                self.slots.len() - 1
+            },
+        };
+        self.heap.push(slot);
+        self.sift_up(position);
+        self.handle(slot)
+    }
+
+    // O(log n)
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        let slot = *self.heap.first()?;
+        Some(self.take(slot))
+    }
+
+    // the entry pop hands out next
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|&slot| self.entry(slot))
+    }
+
+    // the handle of the entry pop hands out next
+    pub fn peek_handle(&self) -> Option<QueueHandle> {
+        self.heap.first().map(|&slot| self.handle(slot))
+    }
+
+    pub fn contains(&self, handle: QueueHandle) -> bool {
+        self.live(handle).is_some()
+    }
+
+    pub fn get(&self, handle: QueueHandle) -> Option<(&K, &P)> {
+        self.live(handle).map(|slot| self.entry(slot))
+    }
+
+    // the key can be changed freely, the priority only through change_priority
+    pub fn get_key_mut(&mut self, handle: QueueHandle) -> Option<&mut K> {
+        let slot = self.live(handle)?;
+        self.slots[slot].entry.as_mut().map(|(key, _)| key)
+    }
+
+    // moves the entry up or down to match its new priority, covering both decrease key and increase key.
+    // O(log n), panics when the entry was already popped or removed or the handle is from another queue
+    pub fn change_priority(&mut self, handle: QueueHandle, priority: P) -> &mut Self {
+        self.try_change_priority(handle, priority).expect("stale or foreign queue handle")
+    }
+
+    pub fn try_change_priority(&mut self, handle: QueueHandle, priority: P) -> Result<&mut Self> {
+        let slot = self.live(handle).ok_or(Error::NotFound)?;
+        if let Some((_, current)) = self.slots[slot].entry.as_mut() {
+            *current = priority;
+        }
+
+        let position = self.slots[slot].position;
+        self.sift_up(position);
+        self.sift_down(self.slots[slot].position);
+        Ok(self)
+    }
+
+    // takes the entry out wherever it is in the heap. O(log n), none when it was already popped or removed
+    pub fn remove(&mut self, handle: QueueHandle) -> Option<(K, P)> {
+        let slot = self.live(handle)?;
+        Some(self.take(slot))
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        for &slot in &self.heap {
+            self.slots[slot].entry = None;
+            self.slots[slot].generation += 1;
+            self.free.push(slot);
+        }
+        self.heap.clear();
+        self
+    }
+
+    // in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &P)> + '_ {
+        self.heap.iter().map(move |&slot| self.entry(slot))
+    }
+
+    // ascending under the comparator, the reverse of the order pop hands them out in. O(n log n)
+    pub fn into_sorted_vec(mut self) -> Vec<(K, P)> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(entry) = self.pop() {
+            sorted.push(entry);
+        }
+        sorted.reverse();
+        sorted
+    }
+
+    fn handle(&self, slot: usize) -> QueueHandle {
+        QueueHandle { queue: self.id, slot, generation: self.slots[slot].generation }
+    }
+
+    // the slot of handle when it still names an entry of this queue
+    fn live(&self, handle: QueueHandle) -> Option<usize> {
+        if handle.queue != self.id {
+            return None;
+        }
+
+        self.slots.get(handle.slot)
+            .filter(|slot| slot.generation == handle.generation && slot.entry.is_some())
+            .map(|_| handle.slot)
+    }
+
+    fn entry(&self, slot: usize) -> (&K, &P) {
+        let (key, priority) = self.slots[slot].entry.as_ref().unwrap();
+        (key, priority)
+    }
+
+    fn priority(&self, position: usize) -> &P {
+        self.entry(self.heap[position]).1
+    }
+
+    // unlinks the slot from the heap by moving the last slot into its place, then frees it
+    fn take(&mut self, slot: usize) -> (K, P) {
+        let position = self.slots[slot].position;
+        let last = self.heap.pop().unwrap();
+        if last != slot {
+            self.heap[position] = last;
+            self.slots[last].position = position;
+            self.sift_up(position);
+            self.sift_down(self.slots[last].position);
+        }
+
+        self.slots[slot].generation += 1;
+        self.free.push(slot);
+        self.slots[slot].entry.take().unwrap()
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.slots[self.heap[a]].position = a;
+        self.slots[self.heap[b]].position = b;
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if (self.compare)(self.priority(position), self.priority(parent)) != Ordering::Greater {
+                break;
+            }
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        let end = self.heap.len();
+        loop {
+            let mut child = 2 * position + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && (self.compare)(self.priority(child + 1), self.priority(child)) == Ordering::Greater {
+                child += 1;
+            }
+            if (self.compare)(self.priority(child), self.priority(position)) != Ordering::Greater {
+                break;
+            }
+            self.swap(position, child);
+            position = child;
+        }
+    }
+}
+
+impl<K, P: Ord> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, P: Ord> FromIterator<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut queue = IndexedPriorityQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<K, P> Extend<(K, P)> for IndexedPriorityQueue<K, P> {
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        for (key, priority) in iter {
+            self.push(key, priority);
+        }
+    }
+}
+
+// the copy keeps the slots and the id, so handles of the original name the same entries in it
+impl<K: Clone, P: Clone> Clone for IndexedPriorityQueue<K, P> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.iter()
+                .map(|slot| Slot { entry: slot.entry.clone(), position: slot.position, generation: slot.generation })
+                .collect(),
+            free: self.free.clone(),
+            heap: self.heap.clone(),
+            compare: self.compare,
+            id: self.id
+        }
+    }
+}
+
+impl<K: fmt::Debug, P: fmt::Debug> fmt::Debug for IndexedPriorityQueue<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexedPriorityQueue;
+    use crate::error::Error;
+
+    #[test]
+    fn ipq_push_pop() {
+        let mut queue = IndexedPriorityQueue::new();
+        assert_eq!(queue.pop(), None);
+        queue.push("b", 2);
+        queue.push("c", 3);
+        queue.push("a", 1);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek(), Some((&"c", &3)));
+        assert_eq!(queue.pop(), Some(("c", 3)));
+        assert_eq!(queue.pop(), Some(("b", 2)));
+        assert_eq!(queue.pop(), Some(("a", 1)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn ipq_change_priority() {
+        let mut queue = IndexedPriorityQueue::new_min();
+        let a = queue.push('a', 10);
+        let b = queue.push('b', 20);
+        let c = queue.push('c', 30);
+        queue.change_priority(c, 5);
+        assert_eq!(queue.peek(), Some((&'c', &5)));
+        queue.change_priority(c, 50).change_priority(b, 1);
+        assert_eq!(queue.peek_handle(), Some(b));
+        assert_eq!(queue.get(a), Some((&'a', &10)));
+        assert_eq!(queue.into_sorted_vec(), vec![('c', 50), ('a', 10), ('b', 1)]);
+    }
+
+    #[test]
+    fn ipq_remove_and_stale_handles() {
+        let mut queue = IndexedPriorityQueue::new();
+        let a = queue.push(1, 1);
+        let b = queue.push(2, 2);
+        assert_eq!(queue.remove(a), Some((1, 1)));
+        assert_eq!(queue.remove(a), None);
+        assert!(!queue.contains(a));
+
+        // the freed slot is reused, but the old handle does not reach the new entry
+        let c = queue.push(3, 3);
+        assert!(queue.contains(c));
+        assert_eq!(queue.get(a), None);
+        assert_eq!(queue.try_change_priority(a, 9).err(), Some(Error::NotFound));
+
+        *queue.get_key_mut(b).unwrap() = 20;
+        queue.clear();
+        assert!(!queue.contains(b));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn ipq_foreign_handles() {
+        let mut a = IndexedPriorityQueue::new();
+        let mut b = IndexedPriorityQueue::new();
+        let in_a = a.push('a', 1);
+        let in_b = b.push('b', 2);
+
+        // both entries sit in slot 0 of their queue, only the queue that handed the handle out accepts it
+        assert!(!a.contains(in_b));
+        assert_eq!(a.get(in_b), None);
+        assert_eq!(a.try_change_priority(in_b, 9).err(), Some(Error::NotFound));
+        assert_eq!(b.remove(in_a), None);
+        assert_eq!((a.len(), b.len()), (1, 1));
+
+        let copy = a.clone();
+        assert_eq!(copy.get(in_a), Some((&'a', &1)));
+        assert_eq!(b.get(in_b), Some((&'b', &2)));
+    }
+
+    #[test]
+    fn ipq_matches_sorting() {
+        let mut queue = IndexedPriorityQueue::new_min();
+        let mut expected = Vec::new();
+        let mut handles = Vec::new();
+        let mut seed = 31u64;
+        for key in 0..200 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            handles.push(queue.push(key, (seed >> 40) % 1000));
+        }
+        for (key, handle) in handles.into_iter().enumerate() {
+            if key % 3 == 0 {
+                queue.remove(handle);
+            } else {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let priority = (seed >> 40) % 1000;
+                queue.change_priority(handle, priority);
+                expected.push((priority, key));
+            }
+        }
+        expected.sort();
+        let mut popped = Vec::new();
+        while let Some((key, priority)) = queue.pop() {
+            popped.push((priority, key));
+        }
+        assert_eq!(popped.iter().map(|p| p.0).collect::<Vec<u64>>(),
+                   expected.iter().map(|p| p.0).collect::<Vec<u64>>());
+        assert_eq!(popped.len(), expected.len());
+    }
+
+    #[test]
+    fn ipq_dijkstra() {
+        // 0 -> 1 (4), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (1), 2 -> 3 (5)
+        let edges = [vec![(1, 4), (2, 1)], vec![(3, 1)], vec![(1, 2), (3, 5)], vec![]];
+        let mut dist = [u32::MAX; 4];
+        let mut queue = IndexedPriorityQueue::new_min();
+        let handles: Vec<_> = (0..4).map(|node| queue.push(node, if node == 0 { 0 } else { u32::MAX })).collect();
+        while let Some((node, d)) = queue.pop() {
+            dist[node] = d;
+            for &(next, weight) in &edges[node] {
+                if let Some((_, &current)) = queue.get(handles[next]) {
+                    if d + weight < current {
+                        queue.change_priority(handles[next], d + weight);
+                    }
+                }
+            }
+        }
+        assert_eq!(dist, [0, 3, 1, 4]);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::error::Result;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// min heaps that meld cheaply and hand out handles to their entries, so code written against the trait can switch
+// between PairingHeap, BinomialHeap and FibonacciHeap. a handle stays valid until its entry is popped, including
+// after the heap is melded into another one. handles of entries in other heaps are refused with NotFound
+pub trait MergeableHeap<T: Ord>: Default {
+    type Handle: Clone;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle;
+
+    // the smallest element, borrowed from the heap
+    fn peek_min(&self) -> Option<&T>;
+
+    fn pop_min(&mut self) -> Option<T>;
+
+    // moves every entry of other into the heap, handles of other keep working on the result
+    fn meld(&mut self, other: Self) -> &mut Self;
+
+    // replaces the element of the entry with a smaller or equal one. refuses with NotFound when the entry was
+    // already popped or is in another heap and with KeyIncreased when element is greater than the current one
+    fn try_decrease_key(&mut self, handle: &Self::Handle, element: T) -> Result<&mut Self>;
+
+    // like try_decrease_key, but panics where it would refuse
+    fn decrease_key(&mut self, handle: &Self::Handle, element: T) -> &mut Self {
+        match self.try_decrease_key(handle, element) {
+            Ok(heap) => heap,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    // pops every element, smallest first
+    fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(element) = self.pop_min() {
+            sorted.push(element);
+        }
+        sorted
+    }
+}
+
+// identifies the heap an entry belongs to, every node holds the id of the heap it was pushed to. meld chains the id
+// of the consumed heap to the id of the heap it went into, so it stays O(1) and the nodes never need rewriting.
+// looking an id up follows the chain and points everything on the way straight at its end
+pub(crate) struct HeapId {
+    merged_into: RefCell<Option<Rc<HeapId>>>
+}
+
+impl HeapId {
+    pub(crate) fn new() -> Rc<HeapId> {
+        Rc::new(HeapId { merged_into: RefCell::new(None) })
+    }
+
+    // the entries of heap id moved into heap into
+    pub(crate) fn merge(id: &Rc<HeapId>, into: &Rc<HeapId>) {
+        *id.merged_into.borrow_mut() = Some(Rc::clone(into));
+    }
+
+    // true when the entry holding entry_id belongs to the heap with id heap, which must not have been melded away
+    pub(crate) fn owns(heap: &Rc<HeapId>, entry_id: &mut Rc<HeapId>) -> bool {
+        let mut end = Rc::clone(entry_id);
+        loop {
+            let next = end.merged_into.borrow().clone();
+            match next {
+                Some(next) => end = next,
+                None => break,
+            }
+        }
+
+        let mut id = Rc::clone(entry_id);
+        while !Rc::ptr_eq(&id, &end) {
+            let next = id.merged_into.replace(Some(Rc::clone(&end))).unwrap();
+            id = next;
+        }
+        *entry_id = end;
+        Rc::ptr_eq(heap, entry_id)
+    }
+}
+
+impl Drop for HeapId {
+    fn drop(&mut self) {
+        // a long chain of melded ids would otherwise be dropped recursively
+        let mut next = self.merged_into.get_mut().take();
+        while let Some(id) = next {
+            next = match Rc::try_unwrap(id) {
+                Ok(id) => id.merged_into.borrow_mut().take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MergeableHeap;
+    use crate::collections::heaps::{BinomialHeap, FibonacciHeap, PairingHeap};
+    use crate::error::Error;
+
+    // pushes, melds, decreases and pops through the trait only, checking every pop against a sorted model
+    fn exercise<H: MergeableHeap<u64>>() {
+        let mut heap = H::default();
+        let mut model: Vec<u64> = Vec::new();
+        let mut handles = Vec::new();
+        let mut seed = 12345u64;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            seed >> 33
+        };
+
+        for round in 0..400 {
+            match next() % 5 {
+                0 | 1 => {
+                    let key = next() % 10_000 + 10_000;
+                    handles.push((heap.push(key), key));
+                    model.push(key);
+                },
+                2 => {
+                    let mut other = H::default();
+                    for _ in 0..next() % 4 {
+                        let key = next() % 10_000 + 10_000;
+                        handles.push((other.push(key), key));
+                        model.push(key);
+                    }
+                    heap.meld(other);
+                },
+                3 if !handles.is_empty() => {
+                    let index = next() as usize % handles.len();
+                    let (handle, key) = handles[index].clone();
+                    let smaller = key - key.min(next() % 100 + round);
+                    if heap.try_decrease_key(&handle, smaller).is_ok() {
+                        let at = model.iter().position(|&x| x == key).unwrap();
+                        model[at] = smaller;
+                        handles[index].1 = smaller;
+                    }
+                },
+                _ => {
+                    model.sort_unstable_by(|a, b| b.cmp(a));
+                    let popped = heap.pop_min();
+                    assert_eq!(popped, model.pop());
+                    if let Some(key) = popped {
+                        // the handle of the popped entry is stale now, find one with that key and drop it
+                        handles.retain(|(handle, k)| *k != key || heap.try_decrease_key(handle, key).is_ok());
+                    }
+                },
+            }
+            assert_eq!(heap.len(), model.len());
+        }
+
+        model.sort_unstable();
+        assert_eq!(heap.into_sorted_vec(), model);
+    }
+
+    // handles only work on the heap holding their entry, which changes when that heap is melded away
+    fn foreign_handles<H: MergeableHeap<u64>>() {
+        let mut a = H::default();
+        let mut b = H::default();
+        let in_a = a.push(5);
+        a.push(7);
+        b.push(3);
+        let in_b = b.push(9);
+
+        assert_eq!(a.try_decrease_key(&in_b, 1).err(), Some(Error::NotFound));
+        assert_eq!(b.try_decrease_key(&in_a, 1).err(), Some(Error::NotFound));
+        assert_eq!((a.len(), *a.peek_min().unwrap()), (2, 5));
+        assert_eq!((b.len(), *b.peek_min().unwrap()), (2, 3));
+
+        a.meld(b);
+        a.decrease_key(&in_b, 1);
+        assert_eq!(a.peek_min(), Some(&1));
+
+        let mut c = H::default();
+        c.meld(a);
+        assert_eq!(H::default().try_decrease_key(&in_a, 0).err(), Some(Error::NotFound));
+        c.decrease_key(&in_a, 0);
+        assert_eq!(c.into_sorted_vec(), vec![0, 1, 3, 7]);
+    }
+
+    #[test]
+    fn mheap_pairing() {
+        exercise::<PairingHeap<u64>>();
+        foreign_handles::<PairingHeap<u64>>();
+    }
+
+    #[test]
+    fn mheap_binomial() {
+        exercise::<BinomialHeap<u64>>();
+        foreign_handles::<BinomialHeap<u64>>();
+    }
+
+    #[test]
+    fn mheap_fibonacci() {
+        exercise::<FibonacciHeap<u64>>();
+        foreign_handles::<FibonacciHeap<u64>>();
+    }
+
+    #[test]
+    fn mheap_long_meld_chain() {
+        // every round melds the heap into a fresh one, chaining the ids of all of them
+        let mut heap = PairingHeap::new();
+        let first = heap.push(100);
+        for _ in 0..100_000 {
+            let mut next = PairingHeap::new();
+            next.meld(heap);
+            heap = next;
+        }
+        heap.decrease_key(&first, 1);
+        assert_eq!(heap.pop_min(), Some(1));
+
+        let mut heap = FibonacciHeap::new();
+        heap.push(1);
+        for _ in 0..100_000 {
+            let mut next = FibonacciHeap::new();
+            next.meld(heap);
+            heap = next;
+        }
+        drop(heap);
+    }
+}
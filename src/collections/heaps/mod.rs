@@ -0,0 +1,13 @@
+pub mod binaryheap;
+pub mod binomialheap;
+pub mod fibonacciheap;
+pub mod indexedpriorityqueue;
+pub mod mergeableheap;
+pub mod pairingheap;
+
+pub use self::binaryheap::BinaryHeap;
+pub use self::binomialheap::{BinomialHandle, BinomialHeap};
+pub use self::fibonacciheap::{FibonacciHandle, FibonacciHeap};
+pub use self::indexedpriorityqueue::{IndexedPriorityQueue, QueueHandle};
+pub use self::mergeableheap::MergeableHeap;
+pub use self::pairingheap::{PairingHandle, PairingHeap};
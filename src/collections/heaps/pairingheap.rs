@@ -0,0 +1,282 @@
+use crate::collections::heaps::mergeableheap::HeapId;
+use crate::collections::heaps::MergeableHeap;
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::{Rc, Weak};
+
+type PairingNodePtr<T> = Rc<RefCell<PairingNode<T>>>;
+type WeakPairingNodePtr<T> = Weak<RefCell<PairingNode<T>>>;
+
+struct PairingNode<T> {
+    element: T,
+    heap: Rc<HeapId>, // the heap the node was pushed to, see HeapId
+    child: Option<PairingNodePtr<T>>, // first child, the others follow through its next links
+    next: Option<PairingNodePtr<T>>, // next sibling
+    prev: WeakPairingNodePtr<T> // previous sibling, the parent for a first child and dangling for the root
+}
+
+// entry of a PairingHeap, goes stale once the entry is popped
+pub struct PairingHandle<T> {
+    node: WeakPairingNodePtr<T>
+}
+
+// a single tree where every node is no greater than its children. push, meld and decrease_key link two trees in
+// O(1), pop_min pairs up the children of the root left to right and folds the pairs right to left, O(log n)
+// amortized
+pub struct PairingHeap<T> {
+    root: Option<PairingNodePtr<T>>,
+    count: usize,
+    id: Rc<HeapId>
+}
+
+impl<T> PairingHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            count: 0,
+            id: HeapId::new()
+        }
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        // the sibling chains can be as long as the heap, so the links are taken apart iteratively
+        let mut stack: Vec<PairingNodePtr<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.next.take());
+        }
+        self.count = 0;
+        self
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    // makes the root with the greater element the first child of the other, both must be roots
+    fn link(a: Option<PairingNodePtr<T>>, b: Option<PairingNodePtr<T>>) -> Option<PairingNodePtr<T>> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, b) => return a.or(b),
+        };
+        let (parent, child) = if b.borrow().element < a.borrow().element { (b, a) } else { (a, b) };
+
+        {
+            let mut node = child.borrow_mut();
+            node.prev = Rc::downgrade(&parent);
+            node.next = parent.borrow_mut().child.take();
+            if let Some(next) = &node.next {
+                next.borrow_mut().prev = Rc::downgrade(&child);
+            }
+        }
+        parent.borrow_mut().child = Some(child);
+        Some(parent)
+    }
+
+    // detaches node and its subtree from the tree, node must not be the root
+    fn cut(node: &PairingNodePtr<T>) {
+        let mut this = node.borrow_mut();
+        let prev = this.prev.upgrade().unwrap();
+        let next = this.next.take();
+        if let Some(next) = &next {
+            next.borrow_mut().prev = Rc::downgrade(&prev);
+        }
+
+        let mut prev = prev.borrow_mut();
+        if prev.child.as_ref().is_some_and(|child| Rc::ptr_eq(child, node)) {
+            prev.child = next;
+        } else {
+            prev.next = next;
+        }
+        this.prev = Weak::new();
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for PairingHeap<T> {
+    type Handle = PairingHandle<T>;
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    // O(1)
+    fn push(&mut self, element: T) -> Self::Handle {
+        let node = Rc::new(RefCell::new(PairingNode {
+            element,
+            heap: Rc::clone(&self.id),
+            child: None,
+            next: None,
+            prev: Weak::new()
+        }));
+        let handle = PairingHandle { node: Rc::downgrade(&node) };
+        self.root = PairingHeap::link(self.root.take(), Some(node));
+        self.count += 1;
+        handle
+    }
+
+    // O(1)
+    fn peek_min(&self) -> Option<&T> {
+        // SAFETY: the root is owned by the heap, which stays borrowed while the reference lives. the handles only
+        // hold weak pointers and every method that borrows a node mutably takes &mut self
+        self.root.as_ref().map(|root| unsafe { &(*root.as_ptr()).element })
+    }
+
+    // O(log n) amortized
+    fn pop_min(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.count -= 1;
+
+        let mut children = Vec::new();
+        let mut child = root.borrow_mut().child.take();
+        while let Some(node) = child {
+            {
+                let mut node = node.borrow_mut();
+                child = node.next.take();
+                node.prev = Weak::new();
+            }
+            children.push(node);
+        }
+
+        // first pass pairs neighbours left to right, second pass melds the pairs right to left
+        let mut pairs = Vec::with_capacity(children.len() / 2 + 1);
+        let mut children = children.into_iter();
+        while let Some(first) = children.next() {
+            pairs.push(PairingHeap::link(Some(first), children.next()));
+        }
+        self.root = pairs.into_iter().rev().fold(None, PairingHeap::link);
+
+        // the handles only hold weak pointers, so the heap held the last strong one
+        Rc::try_unwrap(root).ok().map(|node| node.into_inner().element)
+    }
+
+    // O(1)
+    fn meld(&mut self, mut other: Self) -> &mut Self {
+        self.root = PairingHeap::link(self.root.take(), other.root.take());
+        self.count += other.count;
+        other.count = 0;
+        HeapId::merge(&other.id, &self.id);
+        self
+    }
+
+    // O(1), the entry is cut out with its subtree and linked with the root again
+    fn try_decrease_key(&mut self, handle: &Self::Handle, element: T) -> Result<&mut Self> {
+        let node = handle.node.upgrade().ok_or(Error::NotFound)?;
+        if !HeapId::owns(&self.id, &mut node.borrow_mut().heap) {
+            return Err(Error::NotFound);
+        }
+        if element > node.borrow().element {
+            return Err(Error::KeyIncreased);
+        }
+
+        // only changed once nothing can refuse anymore
+        node.borrow_mut().element = element;
+        if !self.root.as_ref().is_some_and(|root| Rc::ptr_eq(root, &node)) {
+            PairingHeap::cut(&node);
+            self.root = PairingHeap::link(self.root.take(), Some(node));
+        }
+        Ok(self)
+    }
+}
+
+impl<T> Clone for PairingHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: Weak::clone(&self.node)
+        }
+    }
+}
+
+impl<T> fmt::Debug for PairingHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PairingHandle").field("live", &(self.node.strong_count() > 0)).finish()
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PairingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = PairingHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for PairingHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for PairingHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PairingHeap").field("len", &self.count).field("min", &self.peek_min()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PairingHeap;
+    use crate::collections::heaps::MergeableHeap;
+    use crate::error::Error;
+
+    #[test]
+    fn pheap_push_pop() {
+        let mut heap = PairingHeap::new();
+        assert_eq!(heap.pop_min(), None);
+        heap.extend(vec![5, 3, 8, 1, 9, 2]);
+        assert_eq!(heap.len(), 6);
+        assert_eq!(*heap.peek_min().unwrap(), 1);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn pheap_meld() {
+        let mut a: PairingHeap<i32> = vec![4, 10].into_iter().collect();
+        let mut b = PairingHeap::new();
+        let handle = b.push(7);
+        b.push(2);
+        a.meld(b);
+        assert_eq!(a.len(), 4);
+        a.decrease_key(&handle, 1);
+        assert_eq!(a.into_sorted_vec(), vec![1, 2, 4, 10]);
+    }
+
+    #[test]
+    fn pheap_decrease_key() {
+        let mut heap = PairingHeap::new();
+        let handles: Vec<_> = (0..50).map(|x| heap.push(x * 10)).collect();
+        assert_eq!(heap.pop_min(), Some(0));
+        heap.decrease_key(&handles[30], -5).decrease_key(&handles[10], 95);
+        assert_eq!(heap.pop_min(), Some(-5));
+        assert_eq!(heap.try_decrease_key(&handles[0], -1).err(), Some(Error::NotFound));
+        assert_eq!(heap.try_decrease_key(&handles[20], 500).err(), Some(Error::KeyIncreased));
+
+        let mut expected: Vec<i32> = (1..50).filter(|&x| x != 30).map(|x| if x == 10 { 95 } else { x * 10 }).collect();
+        expected.sort();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn pheap_long_chain_drop() {
+        // every push links under the smallest root, which leaves one long sibling chain
+        let mut heap = PairingHeap::new();
+        for x in 0..200_000 {
+            heap.push(x);
+        }
+        drop(heap);
+    }
+}
@@ -0,0 +1,1097 @@
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Index;
+
+// a slot of the arena, vacant slots are chained together into the free list
+enum Slot<T> {
+    Occupied { element: T, next: Option<usize> },
+    Vacant { next_free: Option<usize> }
+}
+
+// singly linked list storing its nodes in one contiguous slab, links are indices into the slab rather than Rc
+// pointers, so there is no refcount traffic, no RefCell borrow checks and no allocation per element
+pub struct ArenaList<T> {
+    slots: Vec<Slot<T>>, // the arena, nodes are never moved while linked
+    head: Option<usize>, // slot of the node at the beginning of the list
+    tail: Option<usize>, // slot of the node at the end of the list
+    free: Option<usize>, // first vacant slot, reused before the slab grows
+    count: usize // the amount of elements in the list
+}
+
+impl<T> ArenaList<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            head: None,
+            tail: None,
+            free: None,
+            count: 0
+        }
+    }
+
+    // reserves room for capacity elements up front, so filling the list does not allocate at all
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    // adds element to beginning of list
+    pub fn prepend(&mut self, element: T) -> &mut Self {
+        let index = self.alloc(element, self.head);
+        self.head = Some(index);
+        if self.tail.is_none() {
+            self.tail = Some(index);
+        }
+
+        self.count += 1;
+        self
+    }
+
+    // add element to the end of the list
+    pub fn append(&mut self, element: T) -> &mut Self {
+        let index = self.alloc(element, None);
+        match self.tail {
+            Some(tail) => self.set_next(tail, Some(index)),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+
+        self.count += 1;
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn iter(&self) -> ArenaListIterator<'_, T> {
+        ArenaListIterator {
+            list: self,
+            current: self.head,
+            len: self.count
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> ArenaListIteratorMut<'_, T> {
+        ArenaListIteratorMut {
+            slots: self.slots.as_mut_ptr(),
+            current: self.head,
+            len: self.count,
+            marker: PhantomData
+        }
+    }
+
+    // applies f to every element in place, front to back
+    pub fn for_each_mut<F>(&mut self, f: F) -> &mut Self
+        where F: FnMut(&mut T) {
+        self.iter_mut().for_each(f);
+        self
+    }
+
+    pub fn insert(&mut self, index: usize, element: T) -> &mut Self {
+        if index > self.count {
+            return self;
+        }
+
+        if index == 0 {
+            return self.prepend(element);
+        }
+
+        if index == self.count {
+            return self.append(element);
+        }
+
+        // normal case, link the new node in after the node before index
+        let prev = self.slot_at(index - 1);
+        let new = self.alloc(element, self.next(prev));
+        self.set_next(prev, Some(new));
+
+        self.count += 1;
+        self
+    }
+
+    // like insert, but reports an out of range index instead of ignoring it
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<&mut Self> {
+        if index > self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.insert(index, element))
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> &mut Self {
+        if index >= self.count {
+            return self;
+        }
+
+        let prev = match index {
+            0 => None,
+            _ => Some(self.slot_at(index - 1)),
+        };
+        self.unlink_after(prev);
+        self
+    }
+
+    // like remove_at, but reports an out of range index instead of ignoring it
+    pub fn try_remove_at(&mut self, index: usize) -> Result<&mut Self> {
+        if index >= self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.remove_at(index))
+    }
+
+    pub fn reverse(&mut self) -> &mut Self {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(index) = cur {
+            cur = self.next(index);
+            self.set_next(index, prev);
+            prev = Some(index);
+        }
+
+        self.tail = self.head;
+        self.head = prev;
+        self
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.slots.clear();
+        self.head = None;
+        self.tail = None;
+        self.free = None;
+        self.count = 0;
+        self
+    }
+
+    // moves every element of other onto the end of this list, leaving other empty.
+    // the two lists live in different arenas so unlike List::append_list this is O(other.size())
+    pub fn append_list(&mut self, other: &mut ArenaList<T>) -> &mut Self {
+        while let Some(element) = other.unlink_front() {
+            self.append(element);
+        }
+
+        self
+    }
+
+    // joins the lists in order
+    pub fn concat<I>(lists: I) -> ArenaList<T>
+        where I: IntoIterator<Item = ArenaList<T>> {
+        let mut result = ArenaList::new() as ArenaList<T>;
+        for mut list in lists {
+            result.append_list(&mut list);
+        }
+        result
+    }
+
+    // splits the list in two at the given index, this list keeps [0, at) and the returned list holds [at, size)
+    // panics when at is past the end of the list, like List::split_off
+    pub fn split_off(&mut self, at: usize) -> ArenaList<T> {
+        if at > self.count {
+            panic!("{}", Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        let mut back = ArenaList::new() as ArenaList<T>;
+        let prev = match at {
+            0 => None,
+            _ => Some(self.slot_at(at - 1)),
+        };
+        while self.count > at {
+            back.append(self.unlink_after(prev));
+        }
+
+        back
+    }
+
+    // like split_off, but reports an out of range index instead of ignoring it
+    pub fn try_split_off(&mut self, at: usize) -> Result<ArenaList<T>> {
+        if at > self.count {
+            return Err(Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        Ok(self.split_off(at))
+    }
+
+    // consumes the list, returning the elements [0, at) and [at, size) as two lists. panics like split_off
+    pub fn split_at(mut self, at: usize) -> (ArenaList<T>, ArenaList<T>) {
+        let back = self.split_off(at);
+        (self, back)
+    }
+
+    // cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front(&self) -> ArenaListCursor<'_, T> {
+        ArenaListCursor {
+            current: self.head,
+            index: 0,
+            list: self
+        }
+    }
+
+    // mutable cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front_mut(&mut self) -> ArenaListCursorMut<'_, T> {
+        ArenaListCursorMut {
+            prev: None,
+            current: self.head,
+            index: 0,
+            list: self
+        }
+    }
+
+    // stable sort, the elements stay in their slots and only the links are rewritten
+    pub fn sort_by<F>(&mut self, mut compare: F) -> &mut Self
+        where F: FnMut(&T, &T) -> Ordering {
+        // nothing to sort
+        if self.count < 2 {
+            return self;
+        }
+
+        let mut order = Vec::with_capacity(self.count);
+        let mut cur = self.head;
+        while let Some(index) = cur {
+            order.push(index);
+            cur = self.next(index);
+        }
+
+        order.sort_by(|a, b| compare(self.element(*a), self.element(*b)));
+
+        for pair in order.windows(2) {
+            self.set_next(pair[0], Some(pair[1]));
+        }
+        self.set_next(order[order.len() - 1], None);
+        self.head = order.first().copied();
+        self.tail = order.last().copied();
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &mut Self
+        where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // takes a slot for a new node, reusing a vacant one when there is any
+    fn alloc(&mut self, element: T, next: Option<usize>) -> usize {
+        let slot = Slot::Occupied { element, next };
+        match self.free {
+            Some(index) => {
+                self.free = match self.slots[index] {
+                    Slot::Vacant { next_free } => next_free,
+                    Slot::Occupied { .. } => unreachable!("occupied slot in the free list"),
+                };
+                self.slots[index] = slot;
+                index
+            },
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            },
+        }
+    }
+
+    // vacates a slot that is no longer linked and hands back its element
+    fn dealloc(&mut self, index: usize) -> T {
+        let slot = mem::replace(&mut self.slots[index], Slot::Vacant { next_free: self.free });
+        self.free = Some(index);
+        match slot {
+            Slot::Occupied { element, .. } => element,
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    fn next(&self, index: usize) -> Option<usize> {
+        match &self.slots[index] {
+            Slot::Occupied { next, .. } => *next,
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    fn set_next(&mut self, index: usize, link: Option<usize>) {
+        match &mut self.slots[index] {
+            Slot::Occupied { next, .. } => *next = link,
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    fn element(&self, index: usize) -> &T {
+        match &self.slots[index] {
+            Slot::Occupied { element, .. } => element,
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    fn element_mut(&mut self, index: usize) -> &mut T {
+        match &mut self.slots[index] {
+            Slot::Occupied { element, .. } => element,
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    // slot of the node at the given position, the position must be in range
+    fn slot_at(&self, position: usize) -> usize {
+        let mut index = self.head.unwrap();
+        for _ in 0..position {
+            index = self.next(index).unwrap();
+        }
+        index
+    }
+
+    // links a new node in after prev (at the head when prev is none) and hands back its slot
+    fn link_after(&mut self, prev: Option<usize>, element: T) -> usize {
+        let next = match prev {
+            Some(prev) => self.next(prev),
+            None => self.head,
+        };
+        let index = self.alloc(element, next);
+
+        match prev {
+            Some(prev) => self.set_next(prev, Some(index)),
+            None => self.head = Some(index),
+        }
+
+        if next.is_none() {
+            self.tail = Some(index);
+        }
+
+        self.count += 1;
+        index
+    }
+
+    // unlinks the node after prev (the head when prev is none) and hands back its element
+    fn unlink_after(&mut self, prev: Option<usize>) -> T {
+        let index = match prev {
+            Some(prev) => self.next(prev).unwrap(),
+            None => self.head.unwrap(),
+        };
+        let next = self.next(index);
+
+        match prev {
+            Some(prev) => self.set_next(prev, next),
+            None => self.head = next,
+        }
+
+        if next.is_none() {
+            self.tail = prev;
+        }
+
+        self.count -= 1;
+        let element = self.dealloc(index);
+
+        // special case removed last node in list, start over with an empty slab but keep its capacity
+        if self.count == 0 {
+            self.slots.clear();
+            self.free = None;
+        }
+
+        element
+    }
+
+    fn unlink_front(&mut self) -> Option<T> {
+        // nothing to unlink
+        self.head?;
+        Some(self.unlink_after(None))
+    }
+}
+
+impl<T: Ord> ArenaList<T> {
+    pub fn sort(&mut self) -> &mut Self {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    pub fn sort_unstable(&mut self) -> &mut Self {
+        self.sort()
+    }
+}
+
+impl<T: PartialOrd> ArenaList<T> {
+    pub fn is_sorted(&self) -> bool {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+}
+
+impl<T: PartialEq> ArenaList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+
+    // removes the first occurrence of element
+    pub fn remove(&mut self, element: &T) -> &mut Self {
+        let _ = self.try_remove(element);
+        self
+    }
+
+    // like remove, but reports when the element is not in the list
+    pub fn try_remove(&mut self, element: &T) -> Result<&mut Self> {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(index) = cur {
+            if self.element(index) == element {
+                self.unlink_after(prev);
+                return Ok(self);
+            }
+            prev = cur;
+            cur = self.next(index);
+        }
+
+        Err(Error::NotFound)
+    }
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for ArenaList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut list = ArenaList::with_capacity(vec.len()) as ArenaList<T>;
+        for x in vec {
+            list.append(x);
+        }
+        list
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ArenaList<T> {
+    fn from(array: [T; N]) -> Self {
+        IntoIterator::into_iter(array).collect()
+    }
+}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = ArenaList::new() as ArenaList<T>;
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for ArenaList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.slots.reserve(iter.size_hint().0);
+        for element in iter {
+            self.append(element);
+        }
+    }
+}
+
+impl<'a, T: Clone> Extend<&'a T> for ArenaList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T> IntoIterator for ArenaList<T> {
+    type Item = T;
+    type IntoIter = ArenaListIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArenaListIntoIterator { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaList<T> {
+    type Item = &'a T;
+    type IntoIter = ArenaListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaList<T> {
+    type Item = &'a mut T;
+    type IntoIter = ArenaListIteratorMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// the copy is compacted, its slab holds the elements in list order without vacant slots
+impl<T: Clone> Clone for ArenaList<T> {
+    fn clone(&self) -> Self {
+        let mut list = ArenaList::with_capacity(self.count) as ArenaList<T>;
+        list.extend(self.iter());
+        list
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArenaList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArenaList<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArenaList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArenaList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArenaList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.count);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for ArenaList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.count {
+            panic!("index out of bounds: the len is {} but the index is {}", self.count, index);
+        }
+
+        self.element(self.slot_at(index))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArenaList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// a cursor points at an element of the list or at the "ghost" position, which sits past the tail and before the head.
+// works like the List cursor, but holds slot indices instead of node pointers
+pub struct ArenaListCursor<'a, T> {
+    current: Option<usize>, // none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a ArenaList<T>
+}
+
+impl<'a, T> ArenaListCursor<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.list.next(current);
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            },
+        }
+    }
+
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        let list = self.list;
+        self.current.map(|current| list.element(current))
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let list = self.list;
+        match self.current {
+            Some(current) => list.next(current).map(|next| list.element(next)),
+            None => list.head.map(|head| list.element(head)),
+        }
+    }
+}
+
+// like ArenaListCursor but able to edit the list, every edit is O(1) as the cursor also tracks the slot before it
+pub struct ArenaListCursorMut<'a, T> {
+    prev: Option<usize>, // slot before current, none when current is the head or at the ghost position
+    current: Option<usize>, // none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a mut ArenaList<T>
+}
+
+impl<'a, T> ArenaListCursorMut<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.list.next(current);
+                // at the ghost position there is no slot before the cursor
+                self.prev = self.current.map(|_| current);
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head;
+                self.prev = None;
+                self.index = 0;
+            },
+        }
+    }
+
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        let current = self.current?;
+        Some(self.list.element_mut(current))
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(current) => self.list.next(current)?,
+            None => self.list.head?,
+        };
+        Some(self.list.element_mut(next))
+    }
+
+    // read only view of the cursor at the same position
+    pub fn as_cursor(&self) -> ArenaListCursor<'_, T> {
+        ArenaListCursor {
+            current: self.current,
+            index: self.index,
+            list: self.list
+        }
+    }
+
+    // inserts after the current element, at the ghost position the element becomes the new head
+    pub fn insert_after(&mut self, element: T) {
+        self.list.link_after(self.current, element);
+        if self.current.is_none() {
+            self.index = self.list.count;
+        }
+    }
+
+    // inserts before the current element, at the ghost position the element becomes the new tail
+    pub fn insert_before(&mut self, element: T) {
+        match self.current {
+            Some(_) => {
+                self.prev = Some(self.list.link_after(self.prev, element));
+                self.index += 1;
+            },
+            None => {
+                self.list.append(element);
+                self.index = self.list.count;
+            },
+        }
+    }
+
+    // removes the current element and moves the cursor to the element after it
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        self.current = self.list.next(current);
+        let element = self.list.unlink_after(self.prev);
+
+        if self.current.is_none() {
+            // the tail was removed, the cursor ends up at the ghost position
+            self.prev = None;
+        }
+
+        Some(element)
+    }
+
+    // moves every element after the current one into a new list, at the ghost position the whole list is moved.
+    // the elements after the cursor change arenas, so unlike List this is O(moved elements)
+    pub fn split_after(&mut self) -> ArenaList<T> {
+        match self.current {
+            Some(current) => {
+                let mut back = ArenaList::new() as ArenaList<T>;
+                while self.list.next(current).is_some() {
+                    back.append(self.list.unlink_after(Some(current)));
+                }
+                back
+            },
+            None => {
+                self.index = 0;
+                mem::take(self.list)
+            },
+        }
+    }
+
+    // moves every element of other in after the current element, at the ghost position they become the new front.
+    // O(other.size()) like append_list
+    pub fn splice_after(&mut self, other: ArenaList<T>) {
+        let mut at = self.current;
+        for element in other {
+            at = Some(self.list.link_after(at, element));
+        }
+
+        if self.current.is_none() {
+            self.index = self.list.count;
+        }
+    }
+}
+
+pub struct ArenaListIterator<'a, T> {
+    list: &'a ArenaList<T>,
+    current: Option<usize>,
+    len: usize // elements left to yield
+}
+
+pub struct ArenaListIteratorMut<'a, T> {
+    slots: *mut Slot<T>, // start of the slab, the iterator holds the only borrow of it
+    current: Option<usize>,
+    len: usize, // elements left to yield
+    marker: PhantomData<&'a mut T> // the iterator mutably borrows the list it was created from
+}
+
+pub struct ArenaListIntoIterator<T> {
+    list: ArenaList<T>
+}
+
+impl<'a, T> Iterator for ArenaListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.current?;
+        self.current = self.list.next(index);
+        self.len -= 1;
+        Some(self.list.element(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ArenaListIterator<'a, T> {}
+
+impl<'a, T> Iterator for ArenaListIteratorMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.current?;
+
+        // SAFETY: the slab is borrowed mutably for 'a and cannot grow or shrink while the iterator lives, so the
+        // slot stays valid. the list holds no cycles, so every slot is visited at most once and the returned
+        // references never alias.
+        match unsafe { &mut *self.slots.add(index) } {
+            Slot::Occupied { element, next } => {
+                self.current = *next;
+                self.len -= 1;
+                Some(element)
+            },
+            Slot::Vacant { .. } => unreachable!("vacant slot in the list"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ArenaListIteratorMut<'a, T> {}
+
+impl<T> Iterator for ArenaListIntoIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.unlink_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.count, Some(self.list.count))
+    }
+}
+
+impl<T> ExactSizeIterator for ArenaListIntoIterator<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::ArenaList;
+    use crate::error::Error;
+
+    #[test]
+    fn alist_new() {
+        let list = ArenaList::new() as ArenaList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.capacity(), 0);
+        assert!(ArenaList::<i32>::with_capacity(16).capacity() >= 16);
+    }
+
+    #[test]
+    fn alist_prepend_append() {
+        let mut list = ArenaList::new() as ArenaList<i32>;
+        list.append(1).prepend(0).append(2);
+        assert_eq!(list, ArenaList::from(vec![0, 1, 2]));
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.head, Some(1));
+        assert_eq!(list.tail, Some(2));
+    }
+
+    #[test]
+    fn alist_count_index_has() {
+        let list = ArenaList::from([1, 2, 3, 2]);
+        assert_eq!(list.count(&2), 2);
+        assert_eq!(list.index(&3), Some(2));
+        assert_eq!(list.index(&10), None);
+        assert!(list.has(&1));
+        assert!(!list.has(&100));
+    }
+
+    #[test]
+    fn alist_insert() {
+        let mut list = ArenaList::from([0, 1, 3, 4, 5]);
+        list.insert(2, 2).insert(6, 6);
+        assert_eq!(list, ArenaList::from([0, 1, 2, 3, 4, 5, 6]));
+        list.insert(0, 10).insert(8, 7).insert(100, 100);
+        assert_eq!(list, ArenaList::from([10, 0, 1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(list.try_insert(10, 0), Err(Error::IndexOutOfBounds { index: 10, len: 9 }));
+    }
+
+    #[test]
+    fn alist_remove() {
+        let mut list = ArenaList::from([0, 6, 1, 2, 3, 4, 5, 1]);
+        assert_eq!(list.remove(&2), &mut ArenaList::from([0, 6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&0), &mut ArenaList::from([6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&1), &mut ArenaList::from([6, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&1), &mut ArenaList::from([6, 3, 4, 5]));
+        assert_eq!(list.try_remove(&1), Err(Error::NotFound));
+        list.append(7);
+        assert_eq!(list, ArenaList::from([6, 3, 4, 5, 7]));
+    }
+
+    #[test]
+    fn alist_remove_at() {
+        let mut list = ArenaList::from([0, 1, 2, 3]);
+        assert_eq!(list.remove_at(2), &mut ArenaList::from([0, 1, 3]));
+        assert_eq!(list.remove_at(0), &mut ArenaList::from([1, 3]));
+        assert_eq!(list.remove_at(1), &mut ArenaList::from([1]));
+        assert_eq!(list.try_remove_at(1), Err(Error::IndexOutOfBounds { index: 1, len: 1 }));
+        assert_eq!(list.remove_at(0), &mut ArenaList::from([]));
+        assert_eq!(list.remove_at(0), &mut ArenaList::from([]));
+    }
+
+    #[test]
+    fn alist_reuses_vacant_slots() {
+        let mut list = ArenaList::from([0, 1, 2, 3]);
+        list.remove_at(1).remove_at(1);
+        list.append(4).append(5);
+        assert_eq!(list.slots.len(), 4);
+        assert_eq!(list, ArenaList::from([0, 3, 4, 5]));
+
+        list.append(6);
+        assert_eq!(list.slots.len(), 5);
+    }
+
+    #[test]
+    fn alist_reverse() {
+        let mut list = ArenaList::from([1, 2, 3]);
+        assert_eq!(list.reverse(), &mut ArenaList::from([3, 2, 1]));
+        list.append(0);
+        assert_eq!(list, ArenaList::from([3, 2, 1, 0]));
+
+        let mut list = ArenaList::new() as ArenaList<i32>;
+        assert_eq!(list.reverse(), &mut ArenaList::from([]));
+    }
+
+    #[test]
+    fn alist_clear() {
+        let mut list = ArenaList::from([1, 2, 3]);
+        assert_eq!(list.clear(), &mut ArenaList::from([]));
+        assert!(list.is_empty());
+        list.append(1);
+        assert_eq!(list, ArenaList::from([1]));
+    }
+
+    #[test]
+    fn alist_sort() {
+        let mut list = ArenaList::from([(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd')]);
+        list.sort_by_key(|pair| pair.0);
+        assert_eq!(list, ArenaList::from([(0, 'd'), (1, 'b'), (2, 'a'), (2, 'c')]));
+        assert!(list.iter().map(|pair| pair.0).eq(vec![0, 1, 2, 2]));
+
+        let mut list = ArenaList::from([5, 3, 9, 1]);
+        list.sort().append(10);
+        assert_eq!(list, ArenaList::from([1, 3, 5, 9, 10]));
+        assert!(list.is_sorted());
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list, ArenaList::from([10, 9, 5, 3, 1]));
+    }
+
+    #[test]
+    fn alist_iter_mut() {
+        let mut list = ArenaList::from([1, 2, 3]);
+        list.remove_at(0).prepend(0);
+        for x in &mut list {
+            *x *= 10;
+        }
+        assert_eq!(list, ArenaList::from([0, 20, 30]));
+        list.for_each_mut(|x| *x += 1);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 21, 31]);
+        assert_eq!(list.iter_mut().len(), 3);
+    }
+
+    #[test]
+    fn alist_append_list_and_split() {
+        let mut list1 = ArenaList::from([0, 1]);
+        let mut list2 = ArenaList::from([2, 3]);
+        list1.append_list(&mut list2);
+        assert_eq!(list1, ArenaList::from([0, 1, 2, 3]));
+        assert!(list2.is_empty());
+
+        let mut back = list1.split_off(1);
+        assert_eq!(list1, ArenaList::from([0]));
+        assert_eq!(back, ArenaList::from([1, 2, 3]));
+        back.append(4);
+        list1.append(5);
+        assert_eq!(back, ArenaList::from([1, 2, 3, 4]));
+        assert_eq!(list1, ArenaList::from([0, 5]));
+        assert_eq!(list1.try_split_off(3), Err(Error::IndexOutOfBounds { index: 3, len: 2 }));
+
+        let (front, back) = ArenaList::concat(vec![list1, back]).split_at(3);
+        assert_eq!(front, ArenaList::from([0, 5, 1]));
+        assert_eq!(back, ArenaList::from([2, 3, 4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 3")]
+    fn alist_split_off_out_of_bounds() {
+        let mut list = ArenaList::from([0, 1]);
+        list.split_off(3);
+    }
+
+    #[test]
+    fn alist_cursor() {
+        let list = ArenaList::from([1, 2, 3]);
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&2));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.current(), cursor.index()), (Some(&3), Some(2)));
+
+        // the ghost position sits between the tail and the head
+        cursor.move_next();
+        assert_eq!((cursor.current(), cursor.index()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&1));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!((ArenaList::new() as ArenaList<i32>).cursor_front().current(), None);
+    }
+
+    #[test]
+    fn alist_cursor_mut_edit() {
+        let mut list = ArenaList::from([1, 3]);
+        let mut cursor = list.cursor_front_mut();
+        *cursor.current().unwrap() = 0;
+        cursor.insert_after(2);
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.insert_before(-1);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(4);
+        assert_eq!(cursor.as_cursor().current(), Some(&3));
+        list.append(5);
+        assert_eq!(list, ArenaList::from([-1, 0, 2, 3, 4, 5]));
+
+        let mut list = ArenaList::new() as ArenaList<i32>;
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(list, ArenaList::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn alist_cursor_mut_remove() {
+        let mut list = ArenaList::from([String::from("a"), String::from("b"), String::from("c")]);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(String::from("a")));
+        assert_eq!(cursor.current(), Some(&mut String::from("b")));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(String::from("c")));
+        assert_eq!(cursor.remove_current(), None);
+        list.append(String::from("d"));
+        assert_eq!(list, ArenaList::from([String::from("b"), String::from("d")]));
+
+        let mut list = ArenaList::from([1]);
+        assert_eq!(list.cursor_front_mut().remove_current(), Some(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn alist_cursor_mut_split_splice() {
+        let mut list = ArenaList::from([1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let mut back = cursor.split_after();
+        assert_eq!(cursor.split_after(), ArenaList::from([]));
+        cursor.splice_after(ArenaList::from([5, 6]));
+        assert_eq!(cursor.peek_next(), Some(&mut 5));
+        list.append(7);
+        back.append(8);
+        assert_eq!(list, ArenaList::from([1, 2, 5, 6, 7]));
+        assert_eq!(back, ArenaList::from([3, 4, 8]));
+
+        // at the ghost position the whole list moves out and spliced lists go to the front
+        let mut cursor = back.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.split_after(), ArenaList::from([3, 4, 8]));
+        cursor.splice_after(ArenaList::from([2]));
+        cursor.splice_after(ArenaList::from([0, 1]));
+        back.append(3);
+        assert_eq!(back, ArenaList::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn alist_traits() {
+        let list: ArenaList<String> = vec!["b", "a"].into_iter().map(String::from).collect();
+        let mut copy = list.clone();
+        copy.extend(&list);
+        copy.extend(vec![String::from("c")]);
+        assert_eq!(copy.size(), 5);
+        assert_eq!(copy[4], "c");
+        assert!(list < copy);
+        assert_eq!(format!("{:?}", list), "[\"b\", \"a\"]");
+        assert_eq!(list.into_iter().collect::<Vec<String>>(), vec![String::from("b"), String::from("a")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn alist_index_out_of_bounds() {
+        let list = ArenaList::from([1]);
+        let _ = list[1];
+    }
+
+    #[test]
+    fn alist_non_copy() {
+        let mut list = ArenaList::new() as ArenaList<String>;
+        list.append(String::from("b")).prepend(String::from("a"));
+        list.remove(&String::from("a"));
+        assert_eq!(list, ArenaList::from(vec![String::from("b")]));
+    }
+}
@@ -0,0 +1,359 @@
+use crate::node::{Node, NodePtr, NodePtrOpt};
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+// singly linked list whose tail links back to its head. only the tail is stored, the head is always one step
+// away, and the head is the current element. rotating just moves the tail pointer around the ring.
+pub struct CircularList<T> {
+    tail: NodePtrOpt<T>, // tail node, its next node is the current element
+    count: usize // the amount of elements in the list
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        Self {
+            tail: None,
+            count: 0
+        }
+    }
+
+    // adds element in front of the current one, it becomes the current element
+    pub fn prepend(&mut self, element: T) -> &mut Self {
+        let node = Node::new(element);
+        match &self.tail {
+            Some(tail) => {
+                node.borrow_mut().set_next_node(tail.borrow().get_next());
+                tail.borrow_mut().set_next_node(Some(node));
+            },
+            None => {
+                // a single node is its own successor
+                node.borrow_mut().set_next_node(Some(Rc::clone(&node)));
+                self.tail = Some(node);
+            },
+        }
+
+        self.count += 1;
+        self
+    }
+
+    // adds element behind the last one, it is reached last when advancing from the current element
+    pub fn append(&mut self, element: T) -> &mut Self {
+        self.prepend(element);
+        self.tail = self.head();
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn head(&self) -> NodePtrOpt<T> {
+        self.tail.as_ref().and_then(|tail| tail.borrow().get_next())
+    }
+
+    pub fn current(&self) -> Option<&T> {
+        self.head().map(|node| CircularListIterator::element(&node))
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the head stays alive through the tail's next link after the temporary Rc from head() is dropped.
+        // the list is borrowed mutably for as long as the reference lives, so no iterator, no other reference into
+        // a node and no method that could unlink the head or `borrow()` it can exist at the same time
+        self.head().map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    // moves on to the next element, the current one becomes the last
+    pub fn advance(&mut self) -> &mut Self {
+        self.rotate_left(1)
+    }
+
+    // the element n places after the current one becomes the current one, O(n % size)
+    pub fn rotate_left(&mut self, n: usize) -> &mut Self {
+        if self.count == 0 {
+            return self;
+        }
+
+        for _ in 0..n % self.count {
+            self.tail = self.head();
+        }
+        self
+    }
+
+    // the element n places before the current one becomes the current one, O(size - n % size)
+    pub fn rotate_right(&mut self, n: usize) -> &mut Self {
+        if self.count == 0 {
+            return self;
+        }
+
+        let steps = self.count - n % self.count;
+        self.rotate_left(steps)
+    }
+
+    // unlinks the current element and hands it back, the next element becomes the current one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let tail = self.tail.as_ref()?;
+        let head = tail.borrow().get_next().unwrap();
+        if self.count == 1 {
+            // break the self loop, otherwise the node keeps itself alive
+            self.tail = None;
+        } else {
+            tail.borrow_mut().set_next_node(head.borrow().get_next());
+        }
+        head.borrow_mut().set_next_node(None);
+        self.count -= 1;
+
+        // the ring no longer holds the node, so this is the last strong reference to it
+        match Rc::try_unwrap(head) {
+            Ok(node) => Some(node.into_inner().element),
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+
+    // visits every element exactly once, starting at the current one
+    pub fn iter(&self) -> CircularListIterator<'_, T> {
+        CircularListIterator {
+            current: self.head(),
+            len: self.count,
+            marker: PhantomData
+        }
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.count = 0;
+
+        // cut the ring open behind the tail, then unlink the nodes one at a time like List does
+        let mut cur = match self.tail.take() {
+            Some(tail) => {
+                let head = tail.borrow().get_next();
+                tail.borrow_mut().set_next_node(None);
+                head
+            },
+            None => None,
+        };
+        while let Some(node) = cur {
+            cur = node.borrow().get_next();
+            node.borrow_mut().set_next_node(None);
+        }
+
+        self
+    }
+}
+
+impl<T: PartialEq> CircularList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    // the distance from the current element to the first occurrence of element
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+}
+
+impl<T> Drop for CircularList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for CircularList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = CircularList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for CircularList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.append(element);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularList<T> {
+    type Item = &'a T;
+    type IntoIter = CircularListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> Clone for CircularList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+// two rings are equal when they hold the same elements in the same order starting at their current elements
+impl<T: PartialEq> PartialEq for CircularList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for CircularList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for CircularList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct CircularListIterator<'a, T> {
+    current: NodePtrOpt<T>,
+    len: usize, // elements left to yield, the ring itself never ends
+    marker: PhantomData<&'a T> // the iterator borrows from the list it was created from
+}
+
+impl<'a, T> CircularListIterator<'a, T> {
+    // SAFETY: every node in the ring is kept alive by the next link of the node before it, the pointer handed in
+    // is only a temporary clone of that link. the list is borrowed immutably for 'a, so the ring is not broken
+    // up or relinked, and no `borrow_mut()` of a node happens outside methods taking &mut self
+    fn element(node: &NodePtr<T>) -> &'a T {
+        unsafe { &(*node.as_ptr()).element }
+    }
+}
+
+impl<'a, T> Iterator for CircularListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let node = self.current.take()?;
+        self.current = node.borrow().get_next();
+        self.len -= 1;
+        Some(CircularListIterator::element(&node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for CircularListIterator<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::CircularList;
+    use std::rc::Rc;
+
+    #[test]
+    fn clist_new() {
+        let mut list = CircularList::new() as CircularList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.current(), None);
+        assert_eq!(list.remove_current(), None);
+        list.advance().rotate_right(3);
+        assert_eq!(list.iter().count(), 0);
+    }
+
+    #[test]
+    fn clist_prepend_append() {
+        let mut list = CircularList::new();
+        list.append(2).append(3).prepend(1);
+        assert_eq!(list.current(), Some(&1));
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3]);
+        assert_eq!(list.size(), 3);
+    }
+
+    #[test]
+    fn clist_iter_visits_each_once() {
+        let list = CircularList::from(vec![1]);
+        assert_eq!(list.iter().collect::<Vec<&i32>>(), vec![&1]);
+        let list: CircularList<i32> = (0..5).collect();
+        assert_eq!(list.iter().len(), 5);
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4]");
+    }
+
+    #[test]
+    fn clist_rotate() {
+        let mut list: CircularList<i32> = (0..5).collect();
+        list.rotate_left(2);
+        assert_eq!(list, CircularList::from(vec![2, 3, 4, 0, 1]));
+        list.rotate_right(3);
+        assert_eq!(list, CircularList::from(vec![4, 0, 1, 2, 3]));
+        list.rotate_left(10).rotate_right(5);
+        assert_eq!(list.current(), Some(&4));
+        list.advance();
+        assert_eq!(list.current(), Some(&0));
+        assert_eq!(list.index(&3), Some(3));
+    }
+
+    #[test]
+    fn clist_round_robin() {
+        let mut tasks = CircularList::from(vec![("a", 2), ("b", 1), ("c", 3)]);
+        let mut order = Vec::new();
+        while let Some(task) = tasks.current_mut() {
+            order.push(task.0);
+            task.1 -= 1;
+            if task.1 == 0 {
+                tasks.remove_current();
+            } else {
+                tasks.advance();
+            }
+        }
+        assert_eq!(order, vec!["a", "b", "c", "a", "c", "c"]);
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn clist_remove_current() {
+        let mut list = CircularList::from(vec![1, 2, 3]);
+        list.advance();
+        assert_eq!(list.remove_current(), Some(2));
+        assert_eq!(list, CircularList::from(vec![3, 1]));
+        assert_eq!(list.remove_current(), Some(3));
+        assert_eq!(list.remove_current(), Some(1));
+        assert_eq!(list.remove_current(), None);
+        list.append(4);
+        assert_eq!(list.current(), Some(&4));
+    }
+
+    #[test]
+    fn clist_drop_breaks_the_cycle() {
+        let tracker = Rc::new(());
+        let mut list = CircularList::new();
+        for _ in 0..3 {
+            list.append(Rc::clone(&tracker));
+        }
+        let single = CircularList::from(vec![Rc::clone(&tracker)]);
+        assert_eq!(Rc::strong_count(&tracker), 5);
+        drop(list);
+        drop(single);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn clist_drop_long() {
+        let list: CircularList<u32> = (0..1_000_000).collect();
+        drop(list);
+    }
+}
@@ -0,0 +1,682 @@
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+// next links own the following node, prev links are weak so the list never forms an Rc cycle
+type DoubleNodePtr<T> = Rc<RefCell<DoubleNode<T>>>;
+type DoubleNodePtrOpt<T> = Option<DoubleNodePtr<T>>;
+type WeakDoubleNodePtrOpt<T> = Option<Weak<RefCell<DoubleNode<T>>>>;
+
+struct DoubleNode<T> {
+    element: T,
+    next: DoubleNodePtrOpt<T>,
+    prev: WeakDoubleNodePtrOpt<T>
+}
+
+impl<T> DoubleNode<T> {
+    fn new(element: T) -> DoubleNodePtr<T> {
+        Rc::new(RefCell::new(Self {
+            element,
+            next: None,
+            prev: None
+        }))
+    }
+}
+
+pub struct DoublyLinkedList<T> {
+    head: DoubleNodePtrOpt<T>, // head node, the node at the beginning of the list
+    tail: DoubleNodePtrOpt<T>, // tail node, the node at the end of the list
+    count: usize // the amount of elements in the list
+}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            count: 0
+        }
+    }
+
+    pub fn push_front(&mut self, element: T) {
+        let new_node = DoubleNode::new(element);
+
+        match self.head.take() {
+            Some(old_head) => {
+                old_head.borrow_mut().prev = Some(Rc::downgrade(&new_node));
+                new_node.borrow_mut().next = Some(old_head);
+            },
+            None => {
+                self.tail = Some(Rc::clone(&new_node));
+            },
+        }
+
+        self.head = Some(new_node);
+        self.count += 1;
+    }
+
+    pub fn push_back(&mut self, element: T) {
+        let new_node = DoubleNode::new(element);
+
+        match self.tail.take() {
+            Some(old_tail) => {
+                new_node.borrow_mut().prev = Some(Rc::downgrade(&old_tail));
+                old_tail.borrow_mut().next = Some(Rc::clone(&new_node));
+            },
+            None => {
+                self.head = Some(Rc::clone(&new_node));
+            },
+        }
+
+        self.tail = Some(new_node);
+        self.count += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head.as_ref().map(Rc::clone)?;
+        Some(self.unlink(head))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail.as_ref().map(Rc::clone)?;
+        Some(self.unlink(tail))
+    }
+
+    // adds element to beginning of list
+    pub fn prepend(&mut self, element: T) -> &mut Self {
+        self.push_front(element);
+        self
+    }
+
+    // add element to the end of the list
+    pub fn append(&mut self, element: T) -> &mut Self {
+        self.push_back(element);
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn iter(&self) -> DoublyLinkedListIterator<'_, T> {
+        DoublyLinkedListIterator {
+            front: self.head.as_ref().map(Rc::clone),
+            back: self.tail.as_ref().map(Rc::clone),
+            len: self.count,
+            marker: PhantomData
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, element: T) -> &mut Self {
+        if index > self.count {
+            return self;
+        }
+
+        if index == 0 {
+            return self.prepend(element);
+        }
+
+        if index == self.count {
+            return self.append(element);
+        }
+
+        // normal case, link the new node in front of the node currently at index
+        let next = self.node_at(index);
+        let prev = next.borrow().prev.as_ref().and_then(Weak::upgrade).unwrap();
+        let new_node = DoubleNode::new(element);
+        new_node.borrow_mut().prev = Some(Rc::downgrade(&prev));
+        new_node.borrow_mut().next = Some(Rc::clone(&next));
+        next.borrow_mut().prev = Some(Rc::downgrade(&new_node));
+        prev.borrow_mut().next = Some(new_node);
+
+        self.count += 1;
+        self
+    }
+
+    // like insert, but reports an out of range index instead of ignoring it
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<&mut Self> {
+        if index > self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.insert(index, element))
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> &mut Self {
+        if index >= self.count {
+            return self;
+        }
+
+        let node = self.node_at(index);
+        self.unlink(node);
+        self
+    }
+
+    // like remove_at, but reports an out of range index instead of ignoring it
+    pub fn try_remove_at(&mut self, index: usize) -> Result<&mut Self> {
+        if index >= self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.remove_at(index))
+    }
+
+    pub fn reverse(&mut self) -> &mut Self {
+        let old_head = self.head.take();
+        let mut cur = old_head.as_ref().map(Rc::clone);
+        let mut last: DoubleNodePtrOpt<T> = None;
+
+        // swap the next and prev links of every node, the strong links now point the other way
+        while let Some(node) = cur {
+            let next = node.borrow_mut().next.take();
+            let prev = node.borrow_mut().prev.take();
+            node.borrow_mut().next = prev.as_ref().and_then(Weak::upgrade);
+            node.borrow_mut().prev = next.as_ref().map(Rc::downgrade);
+            last = Some(node);
+            cur = next;
+        }
+
+        self.head = last;
+        self.tail = old_head;
+        self
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        while self.pop_front().is_some() {}
+        self
+    }
+
+    // stable merge sort that only follows and rewrites the next links. the weak prev links go stale while the runs
+    // are merged and are rebuilt in one pass once the order is final
+    pub fn sort_by<F>(&mut self, mut compare: F) -> &mut Self
+        where F: FnMut(&T, &T) -> Ordering {
+        // nothing to sort
+        if self.count < 2 {
+            return self;
+        }
+
+        // empty the list first, should compare panic it is left empty instead of with prev links that disagree with
+        // the next links. the detached nodes only hold each other through strong next links and drop while unwinding
+        let len = self.count;
+        let head = self.head.take().unwrap();
+        self.tail = None;
+        self.count = 0;
+
+        let (head, tail) = DoublyLinkedList::merge_sort(head, len, &mut compare);
+
+        // the merge only maintains the next links, rebuild the prev links in one pass
+        let mut prev: WeakDoubleNodePtrOpt<T> = None;
+        let mut cur = Some(Rc::clone(&head));
+        while let Some(node) = cur {
+            node.borrow_mut().prev = prev;
+            prev = Some(Rc::downgrade(&node));
+            cur = node.borrow().next.as_ref().map(Rc::clone);
+        }
+
+        self.head = Some(head);
+        self.tail = Some(tail);
+        self.count = len;
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &mut Self
+        where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // returns the node at index walking from whichever end is closer, index must be in range
+    fn node_at(&self, index: usize) -> DoubleNodePtr<T> {
+        if index < self.count / 2 {
+            let mut node = Rc::clone(self.head.as_ref().unwrap());
+            for _ in 0..index {
+                let next = Rc::clone(node.borrow().next.as_ref().unwrap());
+                node = next;
+            }
+            node
+        } else {
+            let mut node = Rc::clone(self.tail.as_ref().unwrap());
+            for _ in index..self.count - 1 {
+                let prev = node.borrow().prev.as_ref().and_then(Weak::upgrade).unwrap();
+                node = prev;
+            }
+            node
+        }
+    }
+
+    // unlinks a node of this list and hands back its element
+    fn unlink(&mut self, node: DoubleNodePtr<T>) -> T {
+        let prev = node.borrow_mut().prev.take().and_then(|prev| prev.upgrade());
+        let next = node.borrow_mut().next.take();
+
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.as_ref().map(Rc::clone),
+            None => self.head = next.as_ref().map(Rc::clone),
+        }
+
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev.as_ref().map(Rc::clone),
+        }
+
+        self.count -= 1;
+
+        // the neighbours now link past the node and only ever pointed back at it weakly, so the pointer passed in is
+        // the last strong one
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().element,
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+
+    // sorts the first len nodes starting at head, returning the new head and tail of the sorted run
+    fn merge_sort<F>(head: DoubleNodePtr<T>, len: usize, compare: &mut F) -> (DoubleNodePtr<T>, DoubleNodePtr<T>)
+        where F: FnMut(&T, &T) -> Ordering {
+        if len == 1 {
+            // cut the run off from whatever followed it
+            head.borrow_mut().next = None;
+            return (Rc::clone(&head), head);
+        }
+
+        // walk to the first node of the right half
+        let mid = len / 2;
+        let mut right = Rc::clone(&head);
+        for _ in 0..mid {
+            let next = Rc::clone(right.borrow().next.as_ref().unwrap());
+            right = next;
+        }
+
+        let left = DoublyLinkedList::merge_sort(head, mid, compare);
+        let right = DoublyLinkedList::merge_sort(right, len - mid, compare);
+        DoublyLinkedList::merge(left, right, compare)
+    }
+
+    // merges two sorted, non empty runs given as (head, tail) pairs
+    fn merge<F>(left: (DoubleNodePtr<T>, DoubleNodePtr<T>), right: (DoubleNodePtr<T>, DoubleNodePtr<T>),
+                compare: &mut F) -> (DoubleNodePtr<T>, DoubleNodePtr<T>)
+        where F: FnMut(&T, &T) -> Ordering {
+        let (left_head, left_tail) = left;
+        let (right_head, right_tail) = right;
+        let mut left: DoubleNodePtrOpt<T> = Some(left_head);
+        let mut right: DoubleNodePtrOpt<T> = Some(right_head);
+        let mut head: DoubleNodePtrOpt<T> = None;
+        let mut last: DoubleNodePtrOpt<T> = None;
+
+        while let (Some(l), Some(r)) = (&left, &right) {
+            // taking from the left run on ties is what keeps the sort stable
+            let take_right = compare(&r.borrow().element, &l.borrow().element) == Ordering::Less;
+            let node = if take_right {
+                let node = right.take().unwrap();
+                right = node.borrow_mut().next.take();
+                node
+            } else {
+                let node = left.take().unwrap();
+                left = node.borrow_mut().next.take();
+                node
+            };
+
+            match &last {
+                Some(last) => last.borrow_mut().next = Some(Rc::clone(&node)),
+                None => head = Some(Rc::clone(&node)),
+            }
+            last = Some(node);
+        }
+
+        // one of the runs is exhausted, the rest of the other one is already in order
+        let (rest, tail) = match left {
+            Some(_) => (left, left_tail),
+            None => (right, right_tail),
+        };
+        last.unwrap().borrow_mut().next = rest;
+
+        (head.unwrap(), tail)
+    }
+}
+
+impl<T: PartialEq> DoublyLinkedList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+
+    // removes the first occurrence of element
+    pub fn remove(&mut self, element: &T) -> &mut Self {
+        let _ = self.try_remove(element);
+        self
+    }
+
+    // like remove, but reports when the element is not in the list
+    pub fn try_remove(&mut self, element: &T) -> Result<&mut Self> {
+        let mut cur = self.head.as_ref().map(Rc::clone);
+        while let Some(node) = cur {
+            if node.borrow().element == *element {
+                self.unlink(node);
+                return Ok(self);
+            }
+            cur = node.borrow().next.as_ref().map(Rc::clone);
+        }
+
+        Err(Error::NotFound)
+    }
+}
+
+impl<T: Clone> DoublyLinkedList<T> {
+    pub fn extend(&mut self, list: &DoublyLinkedList<T>) -> &mut Self {
+        for element in list.iter() {
+            self.append(element.clone());
+        }
+
+        self
+    }
+}
+
+impl<T: Ord> DoublyLinkedList<T> {
+    pub fn sort(&mut self) -> &mut Self {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    // merge sort is already the best fit for linked nodes, so this shares the stable implementation
+    pub fn sort_unstable(&mut self) -> &mut Self {
+        self.sort()
+    }
+}
+
+impl<T: PartialOrd> DoublyLinkedList<T> {
+    pub fn is_sorted(&self) -> bool {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        // unlink front to back, letting the nodes drop one by one would recurse once per node
+        self.clear();
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for DoublyLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut list = DoublyLinkedList::new() as DoublyLinkedList<T>;
+        for x in vec {
+            list.append(x);
+        }
+        list
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct DoublyLinkedListIterator<'a, T> {
+    front: DoubleNodePtrOpt<T>,
+    back: DoubleNodePtrOpt<T>,
+    len: usize, // elements left between front and back, the two ends meet when this reaches 0
+    marker: PhantomData<&'a T> // the iterator borrows from the list it was created from
+}
+
+impl<'a, T> DoublyLinkedListIterator<'a, T> {
+    // SAFETY: the only strong pointer to a node is the next link of the node before it (or head), both owned by
+    // the list, which is borrowed immutably for 'a. walking from the back upgrades the weak prev link into a
+    // temporary Rc, dropping it afterwards leaves the node alive through that strong link. the weak links are
+    // private to the list and only upgraded by its own methods, which all take &mut self when they relink nodes
+    // or call `borrow_mut()`, so the node is neither freed nor mutably borrowed while the reference lives
+    fn element(node: &DoubleNodePtr<T>) -> &'a T {
+        unsafe { &(*node.as_ptr()).element }
+    }
+}
+
+impl<'a, T> Iterator for DoublyLinkedListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let node = self.front.take()?;
+        self.front = node.borrow().next.as_ref().map(Rc::clone);
+        self.len -= 1;
+        Some(DoublyLinkedListIterator::element(&node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DoublyLinkedListIterator<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let node = self.back.take()?;
+        self.back = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+        self.len -= 1;
+        Some(DoublyLinkedListIterator::element(&node))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DoublyLinkedListIterator<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::DoublyLinkedList;
+    use crate::error::Error;
+    use std::rc::Rc;
+
+    // checks that the prev links mirror the next links and that tail and count agree with them
+    fn assert_links<T: PartialEq + std::fmt::Debug>(list: &DoublyLinkedList<T>) {
+        let forward: Vec<&T> = list.iter().collect();
+        let mut backward: Vec<&T> = list.iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), list.size());
+        assert_eq!(list.head.is_none(), list.tail.is_none());
+    }
+
+    #[test]
+    fn dlist_new() {
+        let list = DoublyLinkedList::new() as DoublyLinkedList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.size(), 0);
+        assert!(list.head.is_none());
+        assert!(list.tail.is_none());
+    }
+
+    #[test]
+    fn dlist_push() {
+        let mut list = DoublyLinkedList::new() as DoublyLinkedList<i32>;
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list, DoublyLinkedList::from(vec![1, 2, 3]));
+        assert_links(&list);
+    }
+
+    #[test]
+    fn dlist_pop() {
+        let mut list = DoublyLinkedList::from(vec![1, 2, 3]);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_links(&list);
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.head.is_none());
+        assert!(list.tail.is_none());
+    }
+
+    #[test]
+    fn dlist_pop_non_copy() {
+        let mut list = DoublyLinkedList::from(vec![String::from("a"), String::from("b")]);
+        assert_eq!(list.pop_back(), Some(String::from("b")));
+        assert_eq!(list.pop_front(), Some(String::from("a")));
+    }
+
+    #[test]
+    fn dlist_chain() {
+        let mut list = DoublyLinkedList::new() as DoublyLinkedList<i32>;
+        list.append(1).prepend(0).append(2);
+        assert_eq!(list, DoublyLinkedList::from(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn dlist_iter() {
+        let list = DoublyLinkedList::from(vec![1, 2, 3, 4]);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<i32>>(), vec![4, 3, 2, 1]);
+        assert_eq!(list.iter().len(), 4);
+
+        // both ends meet in the middle without yielding an element twice
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn dlist_count_index_has() {
+        let list = DoublyLinkedList::from(vec![1, 2, 3, 2]);
+        assert_eq!(list.count(&2), 2);
+        assert_eq!(list.count(&0), 0);
+        assert_eq!(list.index(&3), Some(2));
+        assert_eq!(list.index(&10), None);
+        assert!(list.has(&1));
+        assert!(!list.has(&100));
+    }
+
+    #[test]
+    fn dlist_insert() {
+        let mut list = DoublyLinkedList::from(vec![0, 1, 3, 4, 5]);
+        list.insert(2, 2).insert(6, 6);
+        assert_eq!(list, DoublyLinkedList::from(vec![0, 1, 2, 3, 4, 5, 6]));
+        list.insert(0, 10).insert(8, 7).insert(100, 100);
+        assert_eq!(list, DoublyLinkedList::from(vec![10, 0, 1, 2, 3, 4, 5, 6, 7]));
+        list.insert(6, 100);
+        assert_eq!(list, DoublyLinkedList::from(vec![10, 0, 1, 2, 3, 4, 100, 5, 6, 7]));
+        assert_links(&list);
+
+        assert_eq!(list.try_insert(11, 0), Err(Error::IndexOutOfBounds { index: 11, len: 10 }));
+        assert!(list.try_insert(10, 8).is_ok());
+    }
+
+    #[test]
+    fn dlist_remove() {
+        let mut list = DoublyLinkedList::from(vec![0, 6, 1, 2, 3, 4, 5, 1]);
+        assert_eq!(list.remove(&2), &mut DoublyLinkedList::from(vec![0, 6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&0), &mut DoublyLinkedList::from(vec![6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&1), &mut DoublyLinkedList::from(vec![6, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&1), &mut DoublyLinkedList::from(vec![6, 3, 4, 5]));
+        assert_links(&list);
+        assert_eq!(list.try_remove(&1), Err(Error::NotFound));
+    }
+
+    #[test]
+    fn dlist_remove_at() {
+        let mut list = DoublyLinkedList::from(vec![0, 1, 2, 3]);
+        assert_eq!(list.remove_at(2), &mut DoublyLinkedList::from(vec![0, 1, 3]));
+        assert_eq!(list.remove_at(0), &mut DoublyLinkedList::from(vec![1, 3]));
+        assert_eq!(list.remove_at(1), &mut DoublyLinkedList::from(vec![1]));
+        assert_links(&list);
+        assert_eq!(list.try_remove_at(1), Err(Error::IndexOutOfBounds { index: 1, len: 1 }));
+        assert_eq!(list.remove_at(0), &mut DoublyLinkedList::from(vec![]));
+        assert_eq!(list.remove_at(0), &mut DoublyLinkedList::from(vec![]));
+    }
+
+    #[test]
+    fn dlist_reverse() {
+        let mut list = DoublyLinkedList::from(vec![1, 2, 3]);
+        assert_eq!(list.reverse(), &mut DoublyLinkedList::from(vec![3, 2, 1]));
+        assert_links(&list);
+        assert_eq!(list.pop_back(), Some(1));
+
+        let mut list = DoublyLinkedList::from(vec![1]);
+        assert_eq!(list.reverse(), &mut DoublyLinkedList::from(vec![1]));
+        let mut list = DoublyLinkedList::new() as DoublyLinkedList<i32>;
+        assert_eq!(list.reverse(), &mut DoublyLinkedList::from(vec![]));
+    }
+
+    #[test]
+    fn dlist_sort() {
+        let mut list = DoublyLinkedList::from(vec![(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd')]);
+        list.sort_by_key(|pair| pair.0);
+        assert_eq!(list, DoublyLinkedList::from(vec![(0, 'd'), (1, 'b'), (2, 'a'), (2, 'c')]));
+        assert_links(&list);
+        assert!(list.is_sorted());
+
+        let mut list = DoublyLinkedList::from(vec![5, 3, 9, 1]);
+        list.sort().push_back(10);
+        assert_eq!(list, DoublyLinkedList::from(vec![1, 3, 5, 9, 10]));
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list.iter().rev().copied().collect::<Vec<i32>>(), vec![1, 3, 5, 9, 10]);
+    }
+
+    #[test]
+    fn dlist_extend_clear() {
+        let mut list = DoublyLinkedList::from(vec![0, 1]);
+        list.extend(&DoublyLinkedList::from(vec![2, 3]));
+        assert_eq!(list, DoublyLinkedList::from(vec![0, 1, 2, 3]));
+        assert_eq!(list.clear(), &mut DoublyLinkedList::from(vec![]));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn dlist_no_leak() {
+        let tracker = Rc::new(());
+        let mut list = DoublyLinkedList::new();
+        for _ in 0..10 {
+            list.push_back(Rc::clone(&tracker));
+        }
+        list.remove_at(4).reverse();
+        assert_eq!(Rc::strong_count(&tracker), 10);
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn dlist_drop_long() {
+        let mut list = DoublyLinkedList::new();
+        for x in 0..1_000_000 {
+            list.push_back(x);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn dlist_debug() {
+        assert_eq!(format!("{:?}", DoublyLinkedList::from(vec![1, 2])), "[1, 2]");
+    }
+}
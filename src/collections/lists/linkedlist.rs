@@ -1,14 +1,21 @@
+use crate::error::{Error, Result};
 use crate::node::{Node, NodePtr, NodePtrOpt};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, Index, RangeBounds};
 use std::rc::Rc;
 
-#[derive(PartialEq, Debug)]
-pub struct List<T: std::marker::Copy + std::cmp::PartialEq> {
+pub struct List<T> {
     head: NodePtrOpt<T>, // head node, the node at the begging of the list
     tail: NodePtrOpt<T>, // tail node, the node at the end of the list
     count: usize // the amount of elements in the list
 }
 
-impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
+impl<T> List<T> {
     pub fn new() -> Self {
         Self {
             head: None,
@@ -74,7 +81,7 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
         }
     }
 
-    pub fn iter(&self) -> ListIterator<T> {
+    pub fn iter(&self) -> ListIterator<'_, T> {
         match &self.head {
             Some(head) => {
                 ListIterator::new(Some(Rc::clone(head)))
@@ -83,32 +90,14 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
         }
     }
 
-    pub fn count(&self, element: T) -> usize {
-        let mut count = 0;
-        for x in self.iter() {
-            if x == element {
-                count += 1;
-            }
-        }
-        count
-    }
-
-    pub fn index(&self, element: T) -> Option<usize> {
-        let mut i = 0;
-        for x in self.iter() {
-            if x == element {
-                return Some(i);
-            }
-            i += 1;
-        }
-        None
+    pub fn iter_mut(&mut self) -> ListIteratorMut<'_, T> {
+        ListIteratorMut::new(self.head.as_ref().map(Rc::clone))
     }
 
-    pub fn extend(&mut self, list: &List<T>) -> &mut Self {
-        for element in list.iter() {
-            self.append(element);
-        }
-
+    // applies f to every element in place, front to back
+    pub fn for_each_mut<F>(&mut self, f: F) -> &mut Self
+        where F: FnMut(&mut T) {
+        self.iter_mut().for_each(f);
         self
     }
 
@@ -117,14 +106,14 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
             return self;
         }
 
-        // special case insert end of list
+        // special case insert end of list, append does the counting for an empty list
         if index == self.count {
-            self.count += 1;
             match self.tail.as_ref() {
-                Some(mut tail) => {
+                Some(tail) => {
                     let new = Node::new(element);
                     tail.borrow_mut().set_next_node(Some(Rc::clone(&new)));
                     self.tail = Some(Rc::clone(&new));
+                    self.count += 1;
                 },
                 None => {
                     self.append(element);
@@ -135,12 +124,12 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
 
         // special case insert at begging of list
         if index == 0 {
-            self.count += 1;
             match self.head.as_ref() {
                 Some(head) => {
                     let new = Node::new(element);
                     new.borrow_mut().set_next_node(Some(Rc::clone(head)));
                     self.head = Some(Rc::clone(&new));
+                    self.count += 1;
                 },
                 None => {
                     self.append(element);
@@ -152,8 +141,7 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
         // normal case
         self.count += 1;
 
-        let mut i = 0;
-        for mut node in self.iter_node() {
+        for (i, node) in self.iter_node().enumerate() {
             if i == index-1 {
                 let next = node.borrow_mut().get_next();
                 let new = Node::new(element);
@@ -162,20 +150,585 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
 
                 return self;
             }
-            i += 1;
         }
 
         // for what ever reason if this function fails, do nothing and return self to enable chaining
         self
     }
 
-    pub fn remove(&mut self, element: T) -> &mut Self {
+    // like insert, but reports an out of range index instead of ignoring it
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<&mut Self> {
+        if index > self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.insert(index, element))
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> &mut Self {
+        self.take_at(index);
+        self
+    }
+
+    // like remove_at, but reports an out of range index instead of ignoring it
+    pub fn try_remove_at(&mut self, index: usize) -> Result<&mut Self> {
+        if index >= self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.remove_at(index))
+    }
+
+    // like remove_at, but hands back the removed element, none when the index is out of range
+    pub fn take_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.count {
+            return None;
+        }
+
+        // special case head
+        if index == 0 {
+            return self.unlink_front();
+        }
+
+        let prev = self.iter_node().nth(index - 1).unwrap();
+        let node = prev.borrow().get_next().unwrap();
+        let next = node.borrow().get_next();
+
+        // special case the tail is being removed
+        if next.is_none() {
+            self.tail = Some(Rc::clone(&prev));
+        }
+
+        prev.borrow_mut().set_next_node(next);
+        node.borrow_mut().set_next_node(None);
+        self.count -= 1;
+
+        // the list no longer holds the node, so this is the last strong reference to it
+        match Rc::try_unwrap(node) {
+            Ok(node) => Some(node.into_inner().element),
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+
+    // like take_at, but reports an out of range index instead of returning none
+    pub fn try_take_at(&mut self, index: usize) -> Result<T> {
+        let len = self.count;
+        self.take_at(index).ok_or(Error::IndexOutOfBounds { index, len })
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+
+    // O(1)
+    pub fn first(&self) -> Option<&T> {
+        self.iter().next()
+    }
+
+    // O(1), read through the tail pointer
+    pub fn last(&self) -> Option<&T> {
+        // SAFETY: the tail is owned by the list, which stays borrowed while the reference lives, see
+        // ListIterator::next
+        self.tail.as_ref().map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    // removes the last element and hands it back. the list is singly linked, so finding the new tail is O(n)
+    pub fn pop_back(&mut self) -> Option<T> {
+        let last = self.count.checked_sub(1)?;
+        self.take_at(last)
+    }
+
+    // swaps the elements at i and j, out of range indices leave the list untouched
+    pub fn swap(&mut self, i: usize, j: usize) -> &mut Self {
+        let _ = self.try_swap(i, j);
+        self
+    }
+
+    // like swap, but reports an out of range index instead of ignoring it
+    pub fn try_swap(&mut self, i: usize, j: usize) -> Result<&mut Self> {
+        for &index in &[i, j] {
+            if index >= self.count {
+                return Err(Error::IndexOutOfBounds { index, len: self.count });
+            }
+        }
+
+        if i != j {
+            let a = self.iter_node().nth(i).unwrap();
+            let b = self.iter_node().nth(j).unwrap();
+            mem::swap(&mut a.borrow_mut().element, &mut b.borrow_mut().element);
+        }
+        Ok(self)
+    }
+
+    // puts element at index and hands back the element that was there, panics when index is out of range
+    pub fn replace(&mut self, index: usize, element: T) -> T {
+        match self.try_replace(index, element) {
+            Ok(current) => current,
+            Err((err, _)) => panic!("{}", err),
+        }
+    }
+
+    // like replace, but reports an out of range index and gives element back with the error
+    pub fn try_replace(&mut self, index: usize, element: T) -> std::result::Result<T, (Error, T)> {
+        let len = self.count;
+        match self.get_mut(index) {
+            Some(current) => Ok(mem::replace(current, element)),
+            None => Err((Error::IndexOutOfBounds { index, len }, element)),
+        }
+    }
+
+    // the first element pred returns true for
+    pub fn find<P>(&self, mut pred: P) -> Option<&T>
+        where P: FnMut(&T) -> bool {
+        self.iter().find(|x| pred(x))
+    }
+
+    // the first element f maps to some value, and that value
+    pub fn find_map<B, F>(&self, f: F) -> Option<B>
+        where F: FnMut(&T) -> Option<B> {
+        self.iter().find_map(f)
+    }
+
+    // the index of the first element pred returns true for
+    pub fn position<P>(&self, pred: P) -> Option<usize>
+        where P: FnMut(&T) -> bool {
+        self.iter().position(pred)
+    }
+
+    // the index of the last element pred returns true for, found in a single walk from the head
+    pub fn rposition<P>(&self, mut pred: P) -> Option<usize>
+        where P: FnMut(&T) -> bool {
+        self.iter().enumerate().filter(|(_, x)| pred(x)).map(|(i, _)| i).last()
+    }
+
+    pub fn contains_by<P>(&self, pred: P) -> bool
+        where P: FnMut(&T) -> bool {
+        self.iter().any(pred)
+    }
+
+    pub fn count_by<P>(&self, mut pred: P) -> usize
+        where P: FnMut(&T) -> bool {
+        self.iter().filter(|x| pred(x)).count()
+    }
+
+    pub fn reverse(&mut self) -> &mut Self {
+        // nothing to reverse
+        if self.count == 0 {
+            return self;
+        }
+
+        let mut next: NodePtrOpt<T>;
+        let mut prev: NodePtrOpt<T> = None;
+        let mut cur: NodePtrOpt<T> = Some(Rc::clone(self.head.as_ref().unwrap()));
+
+        while cur.as_ref().is_some() {
+            // set next to the next node of current
+            next = cur.as_ref().unwrap().borrow_mut().get_next();
+
+            // set current next node to the prev node
+            match &prev {
+                Some(prv) => {
+                    cur.as_ref().unwrap().borrow_mut().set_next_node(Some(Rc::clone(prv)));
+                },
+                None => {
+                    cur.as_ref().unwrap().borrow_mut().set_next_node(None);
+                },
+            }
+
+            // set prev to the current node
+            prev = Some(Rc::clone(cur.as_ref().unwrap()));
+
+            // set current to the next node
+            match &next {
+                Some(nxt) => {
+                    cur = Some(Rc::clone(nxt));
+                },
+
+                None => {
+                    cur = None;
+                },
+            }
+        }
+        self.tail = Some(Rc::clone(self.head.as_ref().unwrap()));
+        self.head = Some(Rc::clone(prev.as_ref().unwrap()));
+
+        self
+    }
+
+    // moves every node of other onto the end of this list in O(1), leaving other empty
+    pub fn append_list(&mut self, other: &mut List<T>) -> &mut Self {
+        let other_head = match other.head.take() {
+            Some(head) => head,
+            None => return self, // nothing to move
+        };
+
+        match &self.tail {
+            Some(tail) => tail.borrow_mut().set_next_node(Some(other_head)),
+            None => self.head = Some(other_head),
+        }
+
+        self.tail = other.tail.take();
+        self.count += other.count;
+        other.count = 0;
+        self
+    }
+
+    // joins the lists in order, relinking their nodes rather than copying elements
+    pub fn concat<I>(lists: I) -> List<T>
+        where I: IntoIterator<Item = List<T>> {
+        let mut result = List::new() as List<T>;
+        for mut list in lists {
+            result.append_list(&mut list);
+        }
+        result
+    }
+
+    // splits the list in two at the given index, this list keeps [0, at) and the returned list holds [at, size)
+    // panics when at is past the end of the list, like Vec::split_off
+    pub fn split_off(&mut self, at: usize) -> List<T> {
+        if at > self.count {
+            panic!("{}", Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        // special case the whole list moves
+        if at == 0 {
+            let count = self.count;
+            self.count = 0;
+            return List {
+                head: self.head.take(),
+                tail: self.tail.take(),
+                count
+            };
+        }
+
+        // walk to the last node that stays in this list
+        let mut last = Rc::clone(self.head.as_ref().unwrap());
+        for _ in 1..at {
+            let next = last.borrow().get_next().unwrap();
+            last = next;
+        }
+
+        let head = last.borrow().get_next();
+        last.borrow_mut().set_next_node(None);
+        let tail = match head {
+            Some(_) => self.tail.replace(last),
+            None => None, // special case at is the size of the list, nothing moves
+        };
+
+        let count = self.count - at;
+        self.count = at;
+        List { head, tail, count }
+    }
+
+    // like split_off, but reports an out of range index instead of panicking
+    pub fn try_split_off(&mut self, at: usize) -> Result<List<T>> {
+        if at > self.count {
+            return Err(Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        Ok(self.split_off(at))
+    }
+
+    // consumes the list, returning the elements [0, at) and [at, size) as two lists. panics like split_off
+    pub fn split_at(mut self, at: usize) -> (List<T>, List<T>) {
+        let back = self.split_off(at);
+        (self, back)
+    }
+
+    // removes and yields the elements [start, end) of range, the rest of the list is relinked around them.
+    // panics like slicing does when the range is out of order or past the end of the list
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> ListIntoIterator<T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.count,
+        };
+        if start > end {
+            panic!("slice index starts at {} but ends at {}", start, end);
+        }
+        if end > self.count {
+            panic!("range end index {} out of range for slice of length {}", end, self.count);
+        }
+
+        let mut back = self.split_off(end);
+        let drained = self.split_off(start);
+        self.append_list(&mut back);
+        drained.into_iter()
+    }
+
+    // lazily unlinks and yields every element pred returns true for, in one pass over the list.
+    // elements the iterator has not reached when it is dropped stay in the list
+    pub fn extract_if<F>(&mut self, pred: F) -> ListExtractIf<'_, T, F>
+        where F: FnMut(&mut T) -> bool {
+        ListExtractIf {
+            prev: None,
+            current: self.head.as_ref().map(Rc::clone),
+            list: self,
+            pred
+        }
+    }
+
+    // keeps only the elements f returns true for, in one pass
+    pub fn retain<F>(&mut self, mut f: F) -> &mut Self
+        where F: FnMut(&T) -> bool {
+        self.retain_mut(|x| f(x))
+    }
+
+    // like retain, but f may edit the elements it keeps
+    pub fn retain_mut<F>(&mut self, mut f: F) -> &mut Self
+        where F: FnMut(&mut T) -> bool {
+        self.extract_if(|x| !f(x)).for_each(drop);
+        self
+    }
+
+    // removes every element for which same_bucket(element, previous kept element) is true, so a run of elements
+    // in the same bucket is reduced to its first element
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F) -> &mut Self
+        where F: FnMut(&mut T, &mut T) -> bool {
+        let mut prev = match &self.head {
+            Some(head) => Rc::clone(head),
+            None => return self, // nothing to remove
+        };
+
+        let mut cur = prev.borrow().get_next();
+        while let Some(node) = cur {
+            cur = node.borrow().get_next();
+            if same_bucket(&mut node.borrow_mut().element, &mut prev.borrow_mut().element) {
+                prev.borrow_mut().set_next_node(cur.as_ref().map(Rc::clone));
+                node.borrow_mut().set_next_node(None);
+                self.count -= 1;
+
+                // special case the tail was removed
+                if cur.is_none() {
+                    self.tail = Some(Rc::clone(&prev));
+                }
+            } else {
+                prev = node;
+            }
+        }
+
+        self
+    }
+
+    // removes consecutive elements that map to the same key
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F) -> &mut Self
+        where K: PartialEq, F: FnMut(&mut T) -> K {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    // cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.head.as_ref().map(Rc::clone),
+            index: 0,
+            list: self
+        }
+    }
+
+    // mutable cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            prev: None,
+            current: self.head.as_ref().map(Rc::clone),
+            index: 0,
+            list: self
+        }
+    }
+
+    // unlinks the head node and hands back its element
+    fn unlink_front(&mut self) -> Option<T> {
+        let head = self.head.take()?;
+        self.head = head.borrow().get_next();
+        head.borrow_mut().set_next_node(None);
+
+        self.count -= 1;
+        if self.count == 0 {
+            self.tail = None;
+        }
+
+        // the list no longer holds the node, so this is the last strong reference to it
+        match Rc::try_unwrap(head) {
+            Ok(node) => Some(node.into_inner().element),
+            Err(_) => unreachable!("unlinked node is still shared"),
+        }
+    }
+
+    // removes the first element and hands it back, O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.unlink_front()
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.tail = None;
+        self.count = 0;
+
+        // unlink the nodes one at a time, dropping the head would otherwise drop every following node recursively
+        let mut cur = self.head.take();
+        while let Some(node) = cur {
+            cur = node.borrow().get_next();
+            node.borrow_mut().set_next_node(None);
+        }
+
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    // stable merge sort, nodes are relinked in place so elements are never moved or copied
+    pub fn sort_by<F>(&mut self, mut compare: F) -> &mut Self
+        where F: FnMut(&T, &T) -> Ordering {
+        // nothing to sort
+        if self.count < 2 {
+            return self;
+        }
+
+        // detach the nodes while sorting so the list stays consistent (empty) should compare panic
+        let len = self.count;
+        let head = self.head.take().unwrap();
+        self.tail = None;
+        self.count = 0;
+
+        let (head, tail) = List::merge_sort(head, len, &mut compare);
+        self.head = Some(head);
+        self.tail = Some(tail);
+        self.count = len;
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &mut Self
+        where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // sorts the first len nodes starting at head, returning the new head and tail of the sorted run
+    fn merge_sort<F>(head: NodePtr<T>, len: usize, compare: &mut F) -> (NodePtr<T>, NodePtr<T>)
+        where F: FnMut(&T, &T) -> Ordering {
+        if len == 1 {
+            // cut the run off from whatever followed it
+            head.borrow_mut().set_next_node(None);
+            return (Rc::clone(&head), head);
+        }
+
+        // walk to the first node of the right half
+        let mid = len / 2;
+        let mut right = Rc::clone(&head);
+        for _ in 0..mid {
+            let next = right.borrow().get_next().unwrap();
+            right = next;
+        }
+
+        let left = List::merge_sort(head, mid, compare);
+        let right = List::merge_sort(right, len - mid, compare);
+        List::merge(left, right, compare)
+    }
+
+    // merges two sorted, non empty runs given as (head, tail) pairs
+    fn merge<F>(left: (NodePtr<T>, NodePtr<T>), right: (NodePtr<T>, NodePtr<T>), compare: &mut F)
+        -> (NodePtr<T>, NodePtr<T>)
+        where F: FnMut(&T, &T) -> Ordering {
+        let (left_head, left_tail) = left;
+        let (right_head, right_tail) = right;
+        let mut left: NodePtrOpt<T> = Some(left_head);
+        let mut right: NodePtrOpt<T> = Some(right_head);
+        let mut head: NodePtrOpt<T> = None;
+        let mut last: NodePtrOpt<T> = None;
+
+        while let (Some(l), Some(r)) = (&left, &right) {
+            // taking from the left run on ties is what keeps the sort stable
+            let take_right = compare(&r.borrow().element, &l.borrow().element) == Ordering::Less;
+            let node = if take_right {
+                let node = right.take().unwrap();
+                right = node.borrow().get_next();
+                node
+            } else {
+                let node = left.take().unwrap();
+                left = node.borrow().get_next();
+                node
+            };
+
+            match &last {
+                Some(last) => last.borrow_mut().set_next_node(Some(Rc::clone(&node))),
+                None => head = Some(Rc::clone(&node)),
+            }
+            last = Some(node);
+        }
+
+        // one of the runs is exhausted, the rest of the other one is already in order
+        let (rest, tail) = match left {
+            Some(_) => (left, left_tail),
+            None => (right, right_tail),
+        };
+        last.unwrap().borrow_mut().set_next_node(rest);
+
+        (head.unwrap(), tail)
+    }
+}
+
+impl<T: Ord> List<T> {
+    pub fn sort(&mut self) -> &mut Self {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    // merge sort is already the best fit for linked nodes, so this shares the stable implementation
+    pub fn sort_unstable(&mut self) -> &mut Self {
+        self.sort()
+    }
+}
+
+impl<T: PartialOrd> List<T> {
+    pub fn is_sorted(&self) -> bool {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+}
+
+impl<T: PartialEq> List<T> {
+    pub fn count(&self, element: &T) -> usize {
+        let mut count = 0;
+        for x in self.iter() {
+            if x == element {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        for (i, x) in self.iter().enumerate() {
+            if x == element {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        for x in self.iter() {
+            if x == element {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn remove(&mut self, element: &T) -> &mut Self {
         // nothing to remove
         if self.count == 0 {
             return self;
         }
 
-        let mut old_node: NodePtrOpt<T> = None;
+        let mut old_node: NodePtrOpt<T>;
         let mut cur_node: NodePtrOpt<T>;
 
         match &self.head {
@@ -193,7 +746,7 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
         }
 
         // special case of wanting to remove the head element
-        if cur_node.as_ref().unwrap().borrow_mut().element == element {
+        if cur_node.as_ref().unwrap().borrow_mut().element == *element {
             self.head = cur_node.unwrap().borrow_mut().get_next();
 
             // protect against underflow
@@ -213,7 +766,7 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
             old_node = Some(Rc::clone(&cur_node.unwrap()));
             cur_node = Some(Rc::clone(&node));
 
-            if cur_node.as_ref().unwrap().borrow_mut().element == element {
+            if cur_node.as_ref().unwrap().borrow_mut().element == *element {
                 match cur_node.unwrap().borrow_mut().get_next() {
                     Some(ref nxt) => {
                         // normal case, node being removed is not head nor tail
@@ -238,170 +791,386 @@ impl<T: std::marker::Copy + std::cmp::PartialEq> List<T> {
         self
     }
 
-    pub fn remove_at(&mut self, index: usize) -> &mut Self {
-        // nothing to remove
-        if self.count == 0 {
-            return self;
+    // like remove, but reports when the element is not in the list
+    pub fn try_remove(&mut self, element: &T) -> Result<&mut Self> {
+        match self.index(element) {
+            Some(index) => Ok(self.remove_at(index)),
+            None => Err(Error::NotFound),
         }
+    }
 
-        if index > self.count {
-            return self;
-        }
+    // the indices of every occurrence of element, in order
+    pub fn index_of_all(&self, element: &T) -> Vec<usize> {
+        self.iter().enumerate().filter(|(_, x)| *x == element).map(|(i, _)| i).collect()
+    }
 
-        // special case head
-        if index == 0 {
-            let new_head =  match &self.head {
-                Some(head) => {
-                    // protect against underflow
-                    if self.count != 0 {
-                        self.count -= 1;
-                    }
-
-                    match head.borrow_mut().get_next() {
-                        Some(ref h) => {
-                            Some(Rc::clone(h))
-                        },
-                        None => None,
-                    }
-                },
-                None => None, // nothing to remove
-            };
+    // removes every occurrence of element, unlike remove which only takes the first
+    pub fn remove_all(&mut self, element: &T) -> &mut Self {
+        self.retain(|x| x != element)
+    }
 
-            // move head to the next node, thereby removing the original head element
-            self.head = new_head;
+    // removes consecutive repeated elements
+    pub fn dedup(&mut self) -> &mut Self {
+        self.dedup_by(|a, b| a == b)
+    }
+}
 
-            // special case removed last node in list
-            if self.count == 0 {
-                self.tail = None;
-            }
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
 
-            return self;
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut list = List::new() as List<T>;
+        for x in vec {
+            list.append(x);
         }
+        list
+    }
+}
 
-        let mut old_node: NodePtrOpt<T> = None;
-        let mut cur_node: NodePtrOpt<T>;
+impl<T, const N: usize> From<[T; N]> for List<T> {
+    fn from(array: [T; N]) -> Self {
+        IntoIterator::into_iter(array).collect()
+    }
+}
 
-        match &self.head {
-            Some(head) => {
-                cur_node = Some(Rc::clone(head));
-            },
-            None => {
-                cur_node = None;
-            }
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new() as List<T>;
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.append(element);
         }
+    }
+}
 
-        // nothing to remove
-        if cur_node.is_none() {
-            return self;
+// the elements are cloned, so lists of non Copy elements can be extended from another list as well
+impl<'a, T: Clone> Extend<&'a T> for List<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for element in iter {
+            self.append(element.clone());
         }
+    }
+}
 
-        let mut i = 0;
-        for node in self.iter_node() {
-            old_node = Some(Rc::clone(&cur_node.unwrap()));
-            cur_node = Some(Rc::clone(&node));
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = ListIntoIterator<T>;
 
-            if i == index {
-                match cur_node.unwrap().borrow_mut().get_next() {
-                    Some(ref nxt) => {
-                        // normal case, node being removed is not head nor tail
-                        old_node.unwrap().borrow_mut().set_next_node(Some(Rc::clone(nxt)));
-                    },
-                    None => {
-                        // current node is tail, the tail is being removed
-                        old_node.as_ref().unwrap().borrow_mut().set_next_node(None);
-                        self.tail = Some(Rc::clone(&old_node.unwrap()))
-                    },
-                }
+    fn into_iter(self) -> Self::IntoIter {
+        ListIntoIterator { list: self }
+    }
+}
 
-                // protect against underflow
-                if self.count != 0 {
-                    self.count -= 1;
-                }
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = ListIterator<'a, T>;
 
-                return self;
-            }
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = ListIteratorMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// deep copy, the new list gets nodes of its own
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
 
-            i += 1;
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: PartialOrd> PartialOrd for List<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for List<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the length goes first so lists nested in other values hash unambiguously
+        state.write_usize(self.count);
+        for element in self.iter() {
+            element.hash(state);
         }
+    }
+}
 
-        self
+impl<T> Index<usize> for List<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.iter().nth(index) {
+            Some(element) => element,
+            None => panic!("index out of bounds: the len is {} but the index is {}", self.count, index),
+        }
     }
+}
 
-    pub fn reverse(&mut self) -> &mut Self {
-        // nothing to reverse
-        if self.count == 0 {
-            return self;
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// a cursor points at an element of the list or at the "ghost" position, which sits past the tail and before the head.
+// moving next from the tail reaches the ghost and moving next from the ghost wraps around to the head.
+pub struct Cursor<'a, T> {
+    current: NodePtrOpt<T>, // none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a List<T>
+}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(current) => {
+                self.current = current.borrow().get_next();
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head.as_ref().map(Rc::clone);
+                self.index = 0;
+            },
         }
+    }
 
-        let mut next: NodePtrOpt<T> = None;
-        let mut prev: NodePtrOpt<T> = None;
-        let mut cur: NodePtrOpt<T> = Some(Rc::clone(self.head.as_ref().unwrap()));
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
 
-        while cur.as_ref().is_some() {
-            // set next to the next node of current
-            next = cur.as_ref().unwrap().borrow_mut().get_next();
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.as_ref().map(Cursor::element)
+    }
 
-            // set current next node to the prev node
-            match &prev {
-                Some(prv) => {
-                    cur.as_ref().unwrap().borrow_mut().set_next_node(Some(Rc::clone(prv)));
-                },
-                None => {
-                    cur.as_ref().unwrap().borrow_mut().set_next_node(None);
-                },
-            }
+    pub fn peek_next(&self) -> Option<&'a T> {
+        match &self.current {
+            Some(current) => current.borrow().get_next().as_ref().map(Cursor::element),
+            None => self.list.head.as_ref().map(Cursor::element),
+        }
+    }
 
-            // set prev to the current node
-            prev = Some(Rc::clone(cur.as_ref().unwrap()));
+    // SAFETY: the list is borrowed immutably for 'a, see ListIterator::next
+    fn element(node: &NodePtr<T>) -> &'a T {
+        unsafe { &(*node.as_ptr()).element }
+    }
+}
 
-            // set current to the next node
-            match &next {
-                Some(nxt) => {
-                    cur = Some(Rc::clone(nxt));
-                },
+// like Cursor but able to edit the list, every edit is O(1) as the cursor also tracks the node before it
+pub struct CursorMut<'a, T> {
+    prev: NodePtrOpt<T>, // node before current, none when current is the head or at the ghost position
+    current: NodePtrOpt<T>, // none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a mut List<T>
+}
 
-                None => {
-                    cur = None;
-                },
-            }
+impl<'a, T> CursorMut<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(current) => {
+                self.current = current.borrow().get_next();
+                // at the ghost position there is no node before the cursor
+                self.prev = self.current.as_ref().map(|_| current);
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head.as_ref().map(Rc::clone);
+                self.prev = None;
+                self.index = 0;
+            },
         }
-        self.tail = Some(Rc::clone(self.head.as_ref().unwrap()));
-        self.head = Some(Rc::clone(prev.as_ref().unwrap()));
+    }
 
-        self
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
     }
 
-    pub fn clear(&mut self) -> &mut Self {
-        self.tail = None;
-        self.head = None;
-        self.count = 0;
-        self
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the cursor holds the only borrow of the list, so nothing else can reach the node while the
+        // returned reference (tied to the borrow of the cursor) lives
+        self.current.as_ref().map(|node| unsafe { &mut (*node.as_ptr()).element })
     }
 
-    pub fn has(&self, element: T) -> bool {
-        for x in self.iter() {
-            if x == element {
-                return true;
-            }
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match &self.current {
+            Some(current) => current.borrow().get_next(),
+            None => self.list.head.as_ref().map(Rc::clone),
+        };
+
+        // SAFETY: same as current, the node stays owned by the list which the cursor borrows mutably
+        next.map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    // read only view of the cursor at the same position
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            current: self.current.as_ref().map(Rc::clone),
+            index: self.index,
+            list: self.list
         }
+    }
 
-        false
+    // inserts after the current element, at the ghost position the element becomes the new head
+    pub fn insert_after(&mut self, element: T) {
+        match &self.current {
+            Some(current) => {
+                let new = Node::new(element);
+                new.borrow_mut().set_next_node(current.borrow().get_next());
+                current.borrow_mut().set_next_node(Some(Rc::clone(&new)));
+                if new.borrow().get_next().is_none() {
+                    self.list.tail = Some(new);
+                }
+                self.list.count += 1;
+            },
+            None => {
+                self.list.prepend(element);
+                self.index = self.list.count;
+            },
+        }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.count == 0
+    // inserts before the current element, at the ghost position the element becomes the new tail
+    pub fn insert_before(&mut self, element: T) {
+        match &self.current {
+            Some(current) => {
+                let new = Node::new(element);
+                new.borrow_mut().set_next_node(Some(Rc::clone(current)));
+                match &self.prev {
+                    Some(prev) => prev.borrow_mut().set_next_node(Some(Rc::clone(&new))),
+                    None => self.list.head = Some(Rc::clone(&new)),
+                }
+                self.prev = Some(new);
+                self.list.count += 1;
+                self.index += 1;
+            },
+            None => {
+                self.list.append(element);
+                self.index = self.list.count;
+            },
+        }
     }
 
-    pub fn sort(&mut self) {unimplemented!();}
-}
+    // removes the current element and moves the cursor to the element after it
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current.take()?;
+        let next = current.borrow().get_next();
+        current.borrow_mut().set_next_node(None);
 
-impl<T: std::marker::Copy + std::cmp::PartialEq> From<Vec<T>> for List<T> {
-    fn from(vec: Vec<T>) -> Self {
-        let mut list = List::new() as List<T>;
-        for x in vec {
-            list.append(x);
+        match &self.prev {
+            Some(prev) => prev.borrow_mut().set_next_node(next.as_ref().map(Rc::clone)),
+            None => self.list.head = next.as_ref().map(Rc::clone),
+        }
+
+        if next.is_none() {
+            // the tail was removed, the cursor ends up at the ghost position
+            self.list.tail = self.prev.take();
+        }
+
+        self.current = next;
+        self.list.count -= 1;
+
+        // the list no longer holds the node, so this is the last strong reference to it
+        match Rc::try_unwrap(current) {
+            Ok(node) => Some(node.into_inner().element),
+            Err(_) => unreachable!("removed node is still shared"),
+        }
+    }
+
+    // moves every element after the current one into a new list, at the ghost position the whole list is moved
+    pub fn split_after(&mut self) -> List<T> {
+        match &self.current {
+            Some(current) => {
+                let head = current.borrow().get_next();
+                let count = self.list.count - self.index - 1;
+                let tail = match head {
+                    Some(_) => self.list.tail.replace(Rc::clone(current)),
+                    None => None,
+                };
+                current.borrow_mut().set_next_node(None);
+                self.list.count -= count;
+
+                List { head, tail, count }
+            },
+            None => {
+                let list = List {
+                    head: self.list.head.take(),
+                    tail: self.list.tail.take(),
+                    count: self.list.count
+                };
+                self.list.count = 0;
+                self.index = 0;
+                list
+            },
+        }
+    }
+
+    // moves every element of other in after the current element, at the ghost position they become the new front
+    pub fn splice_after(&mut self, mut other: List<T>) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        let count = other.count;
+        other.count = 0;
+
+        match &self.current {
+            Some(current) => {
+                let next = current.borrow().get_next();
+                if next.is_none() {
+                    self.list.tail = Some(Rc::clone(&tail));
+                }
+                tail.borrow_mut().set_next_node(next);
+                current.borrow_mut().set_next_node(Some(head));
+            },
+            None => {
+                match self.list.head.take() {
+                    Some(old_head) => tail.borrow_mut().set_next_node(Some(old_head)),
+                    None => self.list.tail = Some(Rc::clone(&tail)),
+                }
+                self.list.head = Some(head);
+                self.index += count;
+            },
         }
-        list
+
+        self.list.count += count;
     }
 }
 
@@ -409,8 +1178,18 @@ struct ListNodeIterator<T> {
     current: NodePtrOpt<T>
 }
 
-pub struct ListIterator<T> {
-    node_iter: ListNodeIterator<T>
+pub struct ListIterator<'a, T> {
+    node_iter: ListNodeIterator<T>,
+    marker: PhantomData<&'a T> // the iterator borrows from the list it was created from
+}
+
+pub struct ListIntoIterator<T> {
+    list: List<T>
+}
+
+pub struct ListIteratorMut<'a, T> {
+    node_iter: ListNodeIterator<T>,
+    marker: PhantomData<&'a mut T> // the iterator mutably borrows the list it was created from
 }
 
 impl<T> ListNodeIterator<T> {
@@ -421,10 +1200,29 @@ impl<T> ListNodeIterator<T> {
     }
 }
 
-impl<T> ListIterator<T> {
-    pub fn new(start_at: NodePtrOpt<T>) -> Self {
+pub struct ListExtractIf<'a, T, F> {
+    list: &'a mut List<T>,
+    prev: NodePtrOpt<T>, // the last node that stays in the list
+    current: NodePtrOpt<T>, // the next node to check
+    pred: F
+}
+
+impl<'a, T> ListIterator<'a, T> {
+    // only the list may create these, as the yielded references rely on the list keeping its nodes alive
+    fn new(start_at: NodePtrOpt<T>) -> Self {
         ListIterator {
-            node_iter: ListNodeIterator::new(start_at)
+            node_iter: ListNodeIterator::new(start_at),
+            marker: PhantomData
+        }
+    }
+}
+
+impl<'a, T> ListIteratorMut<'a, T> {
+    // only the list may create these, as the yielded references rely on the list keeping its nodes alive
+    fn new(start_at: NodePtrOpt<T>) -> Self {
+        ListIteratorMut {
+            node_iter: ListNodeIterator::new(start_at),
+            marker: PhantomData
         }
     }
 }
@@ -453,22 +1251,91 @@ impl<T> Iterator for ListNodeIterator<T> {
     }
 }
 
-impl<T: Copy> Iterator for ListIterator<T> {
+impl<'a, T> Iterator for ListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the node is owned by the list, which is borrowed immutably for 'a, so the node can neither be
+        // freed nor unlinked while the reference lives. every method taking &self only ever calls `borrow()` on
+        // nodes, so no mutable borrow of the node can be created while the reference lives either.
+        self.node_iter.next().map(|n| unsafe { &(*n.as_ptr()).element })
+    }
+}
+
+impl<T> Iterator for ListIntoIterator<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        return match self.node_iter.next() {
-            Some(n) => {
-                Some(n.borrow_mut().element)
-            },
-            None => None,
+        self.list.unlink_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.count, Some(self.list.count))
+    }
+}
+
+impl<T> ExactSizeIterator for ListIntoIterator<T> {}
+
+impl<'a, T> Iterator for ListIteratorMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the list is borrowed mutably for 'a, so nothing else can reach its nodes while the reference
+        // lives. each node is yielded at most once and the node iterator only borrows a node before it is
+        // yielded, so the returned references never alias.
+        self.node_iter.next().map(|n| unsafe { &mut (*n.as_ptr()).element })
+    }
+}
+
+impl<'a, T, F> Iterator for ListExtractIf<'a, T, F>
+    where F: FnMut(&mut T) -> bool {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.current.take() {
+            self.current = node.borrow().get_next();
+            if !(self.pred)(&mut node.borrow_mut().element) {
+                self.prev = Some(node);
+                continue;
+            }
+
+            // link the nodes on both sides of the extracted one
+            let next = self.current.as_ref().map(Rc::clone);
+            match &self.prev {
+                Some(prev) => prev.borrow_mut().set_next_node(next),
+                None => self.list.head = next,
+            }
+
+            // special case the tail was extracted
+            if self.current.is_none() {
+                self.list.tail = self.prev.as_ref().map(Rc::clone);
+            }
+
+            node.borrow_mut().set_next_node(None);
+            self.list.count -= 1;
+
+            // the list no longer holds the node, so this is the last strong reference to it
+            return match Rc::try_unwrap(node) {
+                Ok(node) => Some(node.into_inner().element),
+                Err(_) => unreachable!("unlinked node is still shared"),
+            };
         }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.count))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::List;
+    use crate::error::Error;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::rc::Rc;
 
     #[test]
     fn list_new() {
@@ -488,8 +1355,8 @@ mod tests {
         list.prepend(1);
         assert_eq!(list.count, 3);
         assert_ne!(list.head.as_ref().unwrap(), list.tail.as_ref().unwrap());
-        assert_eq!(list.head.unwrap().borrow_mut().element, 1);
-        assert_eq!(list.tail.unwrap().borrow_mut().element, 3);
+        assert_eq!(list.head.as_ref().unwrap().borrow_mut().element, 1);
+        assert_eq!(list.tail.as_ref().unwrap().borrow_mut().element, 3);
     }
 
     #[test]
@@ -500,8 +1367,8 @@ mod tests {
         list.append(3);
         assert_eq!(list.count, 3);
         assert_ne!(list.head.as_ref().unwrap(), list.tail.as_ref().unwrap());
-        assert_eq!(list.head.unwrap().borrow_mut().element, 1);
-        assert_eq!(list.tail.unwrap().borrow_mut().element, 3);
+        assert_eq!(list.head.as_ref().unwrap().borrow_mut().element, 1);
+        assert_eq!(list.tail.as_ref().unwrap().borrow_mut().element, 3);
     }
 
     #[test]
@@ -524,10 +1391,8 @@ mod tests {
     fn list_iter_node() {
         let mut list = List::new() as List<i32>;
         list.append(1).append(2).append(3);
-        let mut count = 1;
-        for x in list.iter_node() {
+        for (count, x) in (1..).zip(list.iter_node()) {
             assert_eq!(x.borrow_mut().element, count);
-            count += 1;
         }
     }
 
@@ -535,10 +1400,8 @@ mod tests {
     fn list_iter() {
         let mut list = List::new() as List<i32>;
         list.append(1).append(2).append(3);
-        let mut count = 1;
-        for x in list.iter() {
-            assert_eq!(x, count);
-            count += 1;
+        for (count, x) in (1..).zip(list.iter()) {
+            assert_eq!(*x, count);
         }
     }
 
@@ -546,29 +1409,27 @@ mod tests {
     fn list_count() {
         let mut list = List::new() as List<i32>;
         list.append(1).append(2).append(3).append(2);
-        assert_eq!(list.count(0), 0);
-        assert_eq!(list.count(2), 2);
-        assert_eq!(list.count(3), 1);
+        assert_eq!(list.count(&0), 0);
+        assert_eq!(list.count(&2), 2);
+        assert_eq!(list.count(&3), 1);
     }
 
     #[test]
     fn list_index() {
         let mut list = List::new() as List<i32>;
         list.append(1).append(2).append(3);
-        assert_eq!(list.index(1).unwrap(), 0);
-        assert_eq!(list.index(3).unwrap(), 2);
-        assert_eq!(list.index(10).is_none(), true);
+        assert_eq!(list.index(&1).unwrap(), 0);
+        assert_eq!(list.index(&3).unwrap(), 2);
+        assert!(list.index(&10).is_none());
     }
 
     #[test]
     fn list_from_vec() {
-        let mut vec = vec![0, 1, 2, 3];
-        let mut list = List::from(vec.clone());
+        let vec = vec![0, 1, 2, 3];
+        let list = List::from(vec.clone());
         assert_eq!(list.size(), 4);
-        let mut i =0;
-        for x in list.iter() {
-            assert_eq!(x, vec[i]);
-            i += 1;
+        for (i, x) in list.iter().enumerate() {
+            assert_eq!(*x, vec[i]);
         }
     }
 
@@ -593,29 +1454,39 @@ mod tests {
         assert_eq!(list, List::from(vec![10, 0, 1, 100, 2, 3, 4, 5, 6, 7]));
     }
 
+    #[test]
+    fn list_insert_into_empty() {
+        let mut list = List::new();
+        list.insert(0, 1);
+        assert_eq!(list.size(), 1);
+        assert_eq!(list, List::from([1]));
+        assert_eq!(list.pop_back(), Some(1));
+        assert!(list.is_empty());
+    }
+
     #[test]
     fn list_remove() {
         let mut list = List::from(vec![0, 6, 1, 2, 3, 4, 5, 1]);
 
         // normal case
-        assert_eq!(list.remove(2), &mut List::from(vec![0, 6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&2), &mut List::from(vec![0, 6, 1, 3, 4, 5, 1]));
 
         // head node case
-        assert_eq!(list.remove(0), &mut List::from(vec![6, 1, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&0), &mut List::from(vec![6, 1, 3, 4, 5, 1]));
 
         // duplicate case
         // expected behaviour for duplicate case is that the first occurrence of the duplicate element should be removed
-        assert_eq!(list.remove(1), &mut List::from(vec![6, 3, 4, 5, 1]));
+        assert_eq!(list.remove(&1), &mut List::from(vec![6, 3, 4, 5, 1]));
 
         // tail node case
-        assert_eq!(list.remove(1), &mut List::from(vec![6, 3, 4, 5]));
+        assert_eq!(list.remove(&1), &mut List::from(vec![6, 3, 4, 5]));
 
         // only node in list case
         list = List::from(vec![3]);
-        assert_eq!(list.remove(3), &mut List::from(vec![]));
+        assert_eq!(list.remove(&3), &mut List::from(vec![]));
 
         // empty list case
-        assert_eq!(list.remove(1).remove(3), &mut List::from(vec![]));
+        assert_eq!(list.remove(&1).remove(&3), &mut List::from(vec![]));
     }
 
     #[test]
@@ -656,16 +1527,704 @@ mod tests {
 
     #[test]
     fn list_has() {
-        let mut list = List::from(vec![1, 2, 3]);
-        assert_eq!(list.has(1), true);
-        assert_eq!(list.has(100), false);
+        let list = List::from(vec![1, 2, 3]);
+        assert!(list.has(&1));
+        assert!(!list.has(&100));
     }
 
     #[test]
     fn list_is_empty() {
         let mut list = List::from(vec![1, 2, 3]);
-        assert_eq!(list.is_empty(), false);
+        assert!(!list.is_empty());
         list.clear();
-        assert_eq!(list.is_empty(), true);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn list_non_copy() {
+        let mut list = List::new() as List<String>;
+        list.append(String::from("b")).prepend(String::from("a")).append(String::from("c"));
+        assert_eq!(list.iter().map(|s| s.as_str()).collect::<Vec<&str>>(), vec!["a", "b", "c"]);
+        assert!(list.has(&String::from("b")));
+        assert_eq!(list.index(&String::from("c")), Some(2));
+        assert_eq!(list.count(&String::from("a")), 1);
+
+        list.remove(&String::from("b")).insert(0, String::from("z"));
+        assert_eq!(list, List::from(vec![String::from("z"), String::from("a"), String::from("c")]));
+
+        let mut other = List::from(vec![String::from("d")]);
+        other.extend(&list);
+        assert_eq!(other.size(), 4);
+        assert_eq!(list.size(), 3);
+    }
+
+    #[test]
+    fn list_non_partial_eq() {
+        struct Record {
+            id: u32,
+        }
+
+        let mut list = List::new() as List<Record>;
+        list.append(Record { id: 1 }).append(Record { id: 2 }).reverse();
+        assert_eq!(list.iter().map(|r| r.id).collect::<Vec<u32>>(), vec![2, 1]);
+        list.remove_at(0);
+        assert_eq!(list.size(), 1);
+    }
+
+    #[test]
+    fn list_sort() {
+        let mut list = List::from(vec![5, 3, 9, 1, 3, 0, 7]);
+        assert_eq!(list.sort(), &mut List::from(vec![0, 1, 3, 3, 5, 7, 9]));
+
+        // the tail must follow the sorted order
+        list.append(10);
+        assert_eq!(list, List::from(vec![0, 1, 3, 3, 5, 7, 9, 10]));
+
+        // empty and single element cases
+        let mut list = List::new() as List<i32>;
+        assert_eq!(list.sort(), &mut List::from(vec![]));
+        list.append(1);
+        assert_eq!(list.sort(), &mut List::from(vec![1]));
+    }
+
+    #[test]
+    fn list_sort_large() {
+        let mut seed: u64 = 42;
+        let mut vec = Vec::new();
+        for _ in 0..10_000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            vec.push((seed >> 33) % 1000);
+        }
+
+        let mut list = List::from(vec.clone());
+        list.sort_unstable();
+        vec.sort();
+        assert_eq!(list.size(), vec.len());
+        assert!(list.is_sorted());
+        assert_eq!(list.iter().copied().collect::<Vec<u64>>(), vec);
+    }
+
+    #[test]
+    fn list_sort_by() {
+        let mut list = List::from(vec![1, 4, 2, 3]);
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list, List::from(vec![4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn list_sort_by_key_is_stable() {
+        let mut list = List::from(vec![(2, "a"), (1, "b"), (2, "c"), (1, "d"), (0, "e")]);
+        list.sort_by_key(|pair| pair.0);
+        assert_eq!(list, List::from(vec![(0, "e"), (1, "b"), (1, "d"), (2, "a"), (2, "c")]));
+    }
+
+    #[test]
+    fn list_sort_non_copy() {
+        let mut list = List::from(vec![String::from("pear"), String::from("apple"), String::from("fig")]);
+        list.sort();
+        assert_eq!(list, List::from(vec![String::from("apple"), String::from("fig"), String::from("pear")]));
+    }
+
+    #[test]
+    fn list_is_sorted() {
+        assert!(List::from(vec![1, 2, 2, 3]).is_sorted());
+        assert!(!List::from(vec![1, 3, 2]).is_sorted());
+        assert!((List::new() as List<i32>).is_sorted());
+    }
+
+    #[test]
+    fn list_try_insert() {
+        let mut list = List::from(vec![0, 2]);
+        assert!(list.try_insert(1, 1).is_ok());
+        assert!(list.try_insert(3, 3).is_ok());
+        assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+
+        assert_eq!(list.try_insert(5, 5), Err(Error::IndexOutOfBounds { index: 5, len: 4 }));
+        assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+
+        // chaining is still available through the Ok value
+        list.try_insert(0, 10).unwrap().append(11);
+        assert_eq!(list, List::from(vec![10, 0, 1, 2, 3, 11]));
+
+        let mut empty = List::new();
+        assert!(empty.try_insert(0, 1).is_ok());
+        assert_eq!(empty.size(), 1);
+        assert_eq!(empty, List::from([1]));
+        assert_eq!(empty.take_at(0), Some(1));
+    }
+
+    #[test]
+    fn list_try_remove_at() {
+        let mut list = List::from(vec![0, 1, 2]);
+        assert_eq!(list.try_remove_at(3), Err(Error::IndexOutOfBounds { index: 3, len: 3 }));
+        assert_eq!(list.try_remove_at(2), Ok(&mut List::from(vec![0, 1])));
+        assert_eq!(list.try_remove_at(0), Ok(&mut List::from(vec![1])));
+        assert_eq!(list.try_remove_at(0), Ok(&mut List::from(vec![])));
+        assert_eq!(list.try_remove_at(0), Err(Error::IndexOutOfBounds { index: 0, len: 0 }));
+    }
+
+    #[test]
+    fn list_try_remove() {
+        let mut list = List::from(vec![1, 2, 1]);
+        assert_eq!(list.try_remove(&1), Ok(&mut List::from(vec![2, 1])));
+        assert_eq!(list.try_remove(&3), Err(Error::NotFound));
+        assert_eq!(list.try_remove(&1), Ok(&mut List::from(vec![2])));
+        assert_eq!(list.try_remove(&1), Err(Error::NotFound));
+    }
+
+    #[test]
+    fn list_cursor() {
+        let list = List::from(vec![1, 2, 3]);
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&2));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+
+        // the ghost position sits between the tail and the head
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&1));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+
+        let empty = List::new() as List<i32>;
+        assert_eq!(empty.cursor_front().current(), None);
+    }
+
+    #[test]
+    fn list_cursor_mut_edit() {
+        let mut list = List::from(vec![1, 3]);
+        let mut cursor = list.cursor_front_mut();
+        *cursor.current().unwrap() = 0;
+        cursor.insert_after(2);
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.insert_before(-1);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(4);
+        assert_eq!(cursor.as_cursor().current(), Some(&3));
+        assert_eq!(list, List::from(vec![-1, 0, 2, 3, 4]));
+
+        // the tail is kept up to date
+        list.append(5);
+        assert_eq!(list, List::from(vec![-1, 0, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn list_cursor_mut_ghost_insert() {
+        let mut list = List::new() as List<i32>;
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(list, List::from(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn list_cursor_mut_remove() {
+        let mut list = List::from(vec![String::from("a"), String::from("b"), String::from("c")]);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(String::from("a")));
+        assert_eq!(cursor.current(), Some(&mut String::from("b")));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(String::from("c")));
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(list, List::from(vec![String::from("b")]));
+        list.append(String::from("d"));
+        assert_eq!(list, List::from(vec![String::from("b"), String::from("d")]));
+
+        let mut list = List::from(vec![1]);
+        assert_eq!(list.cursor_front_mut().remove_current(), Some(1));
+        assert_eq!(list, List::from(vec![]));
+    }
+
+    #[test]
+    fn list_cursor_mut_split_after() {
+        let mut list = List::from(vec![1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let mut back = cursor.split_after();
+        assert_eq!(back, List::from(vec![3, 4]));
+        assert_eq!(cursor.split_after(), List::from(vec![]));
+        assert_eq!(list, List::from(vec![1, 2]));
+        list.append(5);
+        back.append(6);
+        assert_eq!(list, List::from(vec![1, 2, 5]));
+        assert_eq!(back, List::from(vec![3, 4, 6]));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.split_after(), List::from(vec![1, 2, 5]));
+        assert_eq!(list, List::from(vec![]));
+    }
+
+    #[test]
+    fn list_cursor_mut_splice_after() {
+        let mut list = List::from(vec![1, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(List::from(vec![2, 3]));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.splice_after(List::from(vec![5, 6]));
+        cursor.splice_after(List::from(vec![]));
+        assert_eq!(list, List::from(vec![1, 2, 3, 4, 5, 6]));
+        list.append(7);
+        assert_eq!(list.size(), 7);
+
+        let mut list = List::new() as List<i32>;
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after(List::from(vec![2]));
+        cursor.splice_after(List::from(vec![0, 1]));
+        assert_eq!(list, List::from(vec![0, 1, 2]));
+        list.append(3);
+        assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn list_iter_mut() {
+        let mut list = List::from(vec![1, 2, 3]);
+        for x in list.iter_mut() {
+            *x *= 10;
+        }
+        assert_eq!(list, List::from(vec![10, 20, 30]));
+
+        // references from the same iteration may be held at once
+        let refs: Vec<&mut i32> = list.iter_mut().collect();
+        assert_eq!(refs.len(), 3);
+        for x in refs {
+            *x += 1;
+        }
+        assert_eq!(list, List::from(vec![11, 21, 31]));
+
+        let mut empty = List::new() as List<i32>;
+        assert_eq!(empty.iter_mut().next(), None);
+    }
+
+    #[test]
+    fn list_for_each_mut() {
+        struct Record {
+            name: String,
+            visits: u32,
+        }
+
+        let mut list = List::new() as List<Record>;
+        list.append(Record { name: String::from("a"), visits: 0 })
+            .append(Record { name: String::from("b"), visits: 2 });
+        list.for_each_mut(|r| r.visits += 1)
+            .for_each_mut(|r| r.name.push('!'));
+        assert_eq!(list.iter().map(|r| (r.name.as_str(), r.visits)).collect::<Vec<(&str, u32)>>(),
+                   vec![("a!", 1), ("b!", 3)]);
+    }
+
+    #[test]
+    fn list_into_iter() {
+        let list = List::from(vec![String::from("a"), String::from("b")]);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(String::from("a")));
+        assert_eq!(iter.next(), Some(String::from("b")));
+        assert_eq!(iter.next(), None);
+
+        let mut list = List::from(vec![1, 2, 3]);
+        for x in &mut list {
+            *x += 1;
+        }
+        let mut sum = 0;
+        for x in &list {
+            sum += x;
+        }
+        assert_eq!(sum, 9);
+        assert_eq!(list.into_iter().collect::<Vec<i32>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn list_from_iter() {
+        let list: List<i32> = (0..4).map(|x| x * 2).collect();
+        assert_eq!(list, List::from(vec![0, 2, 4, 6]));
+        assert_eq!(List::from([1, 2, 3]), List::from(vec![1, 2, 3]));
+
+        // the tail must be set up so appending keeps working
+        let mut list: List<i32> = (0..2).collect();
+        list.append(2);
+        assert_eq!(list, List::from([0, 1, 2]));
+    }
+
+    #[test]
+    fn list_extend_trait() {
+        let mut list = List::from([0]);
+        list.extend(vec![1, 2]);
+        list.extend([3, 4].iter());
+        list.extend(&List::from([5]));
+        assert_eq!(list, List::from([0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn list_clone_is_deep() {
+        let list = List::from(vec![String::from("a"), String::from("b")]);
+        let mut copy = list.clone();
+        assert_eq!(copy, list);
+        copy.iter_mut().for_each(|s| s.push('!'));
+        copy.append(String::from("c"));
+        assert_eq!(list, List::from(vec![String::from("a"), String::from("b")]));
+        assert_eq!(copy, List::from(vec![String::from("a!"), String::from("b!"), String::from("c")]));
+    }
+
+    #[test]
+    fn list_ord() {
+        assert!(List::from([1, 2, 3]) < List::from([1, 3]));
+        assert!(List::from([1, 2]) < List::from([1, 2, 0]));
+        assert!(List::from([2]) > List::from([1, 9, 9]));
+        assert_eq!(List::from([1, 2]).cmp(&List::from([1, 2])), std::cmp::Ordering::Equal);
+        assert_eq!(List::from([1.0, f64::NAN]).partial_cmp(&List::from([1.0, 2.0])), None);
+
+        let mut lists = vec![List::from([3]), List::from([1, 2]), List::from([]), List::from([1])];
+        lists.sort();
+        assert_eq!(lists, vec![List::from([]), List::from([1]), List::from([1, 2]), List::from([3])]);
+    }
+
+    #[test]
+    fn list_hash() {
+        fn hash_of(list: &List<i32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(hash_of(&List::from([1, 2])), hash_of(&List::from(vec![1, 2])));
+        assert_ne!(hash_of(&List::from([1, 2])), hash_of(&List::from([2, 1])));
+        assert_ne!(hash_of(&List::from([1])), hash_of(&List::from([1, 1])));
+    }
+
+    #[test]
+    fn list_index_trait() {
+        let list = List::from([10, 20, 30]);
+        assert_eq!(list[0], 10);
+        assert_eq!(list[2], 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn list_index_trait_out_of_bounds() {
+        let list = List::from([10, 20, 30]);
+        let _ = list[3];
+    }
+
+    #[test]
+    fn list_debug() {
+        assert_eq!(format!("{:?}", List::from([1, 2, 3])), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", List::new() as List<i32>), "[]");
+    }
+
+    #[test]
+    fn list_drop_long() {
+        // dropping recursively would overflow the stack long before reaching this many nodes
+        let mut list = List::new() as List<u32>;
+        for x in 0..10_000_000 {
+            list.append(x);
+        }
+        assert_eq!(list.size(), 10_000_000);
+        drop(list);
+    }
+
+    #[test]
+    fn list_clear_long() {
+        let mut list: List<u32> = (0..1_000_000).collect();
+        list.clear().append(1);
+        assert_eq!(list, List::from([1]));
+    }
+
+    #[test]
+    fn list_drop_releases_elements() {
+        let tracker = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..10 {
+            list.append(Rc::clone(&tracker));
+        }
+        assert_eq!(Rc::strong_count(&tracker), 11);
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn list_append_list() {
+        let mut list1 = List::from([0, 1]);
+        let mut list2 = List::from([2, 3]);
+        list1.append_list(&mut list2).append(4);
+        assert_eq!(list1, List::from([0, 1, 2, 3, 4]));
+        assert_eq!(list2, List::from([]));
+
+        // the emptied list stays usable
+        list2.append(5);
+        assert_eq!(list2, List::from([5]));
+
+        // empty cases
+        let mut empty = List::new() as List<i32>;
+        list1.append_list(&mut empty);
+        assert_eq!(list1.size(), 5);
+        empty.append_list(&mut list2).append(6);
+        assert_eq!(empty, List::from([5, 6]));
+        assert!(list2.is_empty());
+    }
+
+    #[test]
+    fn list_concat() {
+        let batches = vec![List::from([1, 2]), List::from([]), List::from([3]), List::from([4, 5])];
+        let mut list = List::concat(batches);
+        assert_eq!(list, List::from([1, 2, 3, 4, 5]));
+        list.append(6);
+        assert_eq!(list.size(), 6);
+        assert!(List::concat(Vec::<List<i32>>::new()).is_empty());
+    }
+
+    #[test]
+    fn list_split_off() {
+        let mut list = List::from([0, 1, 2, 3, 4]);
+        let mut back = list.split_off(2);
+        assert_eq!(list, List::from([0, 1]));
+        assert_eq!(back, List::from([2, 3, 4]));
+
+        // both tails are correct
+        list.append(10);
+        back.append(11);
+        assert_eq!(list, List::from([0, 1, 10]));
+        assert_eq!(back, List::from([2, 3, 4, 11]));
+
+        // edge cases
+        assert_eq!(list.split_off(3), List::from([]));
+        assert_eq!(list, List::from([0, 1, 10]));
+        assert_eq!(list.split_off(0), List::from([0, 1, 10]));
+        assert!(list.is_empty());
+        list.append(1);
+        assert_eq!(list, List::from([1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 4")]
+    fn list_split_off_out_of_bounds() {
+        List::from([0, 1, 2]).split_off(4);
+    }
+
+    #[test]
+    fn list_try_split_off() {
+        let mut list = List::from([0, 1]);
+        assert_eq!(list.try_split_off(3), Err(Error::IndexOutOfBounds { index: 3, len: 2 }));
+        assert_eq!(list.try_split_off(1), Ok(List::from([1])));
+        assert_eq!(list, List::from([0]));
+    }
+
+    #[test]
+    fn list_split_at() {
+        let (front, back) = List::from([0, 1, 2]).split_at(1);
+        assert_eq!(front, List::from([0]));
+        assert_eq!(back, List::from([1, 2]));
+        assert_eq!(List::concat(vec![front, back]), List::from([0, 1, 2]));
+    }
+
+    #[test]
+    fn list_retain() {
+        let mut list: List<i32> = (0..10).collect();
+        list.retain(|x| x % 3 != 0);
+        assert_eq!(list, List::from([1, 2, 4, 5, 7, 8]));
+        assert_eq!(list.size(), 6);
+
+        // the tail is kept up to date when it is removed
+        list.retain(|x| *x < 8).append(100);
+        assert_eq!(list, List::from([1, 2, 4, 5, 7, 100]));
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        list.append(1);
+        assert_eq!(list, List::from([1]));
+    }
+
+    #[test]
+    fn list_retain_mut() {
+        let mut list = List::from([1, 2, 3, 4]);
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x != 20
+        });
+        assert_eq!(list, List::from([10, 30, 40]));
+    }
+
+    #[test]
+    fn list_remove_all() {
+        let mut list = List::from([2, 1, 2, 2, 3, 2]);
+        list.remove_all(&2).append(4);
+        assert_eq!(list, List::from([1, 3, 4]));
+        list.remove_all(&7);
+        assert_eq!(list.size(), 3);
+    }
+
+    #[test]
+    fn list_dedup() {
+        let mut list = List::from([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        list.dedup().append(5);
+        assert_eq!(list, List::from([1, 2, 3, 1, 4, 5]));
+
+        let mut words = List::from(["apple", "avocado", "banana", "blueberry", "cherry"]);
+        words.dedup_by_key(|w| w.chars().next());
+        assert_eq!(words, List::from(["apple", "banana", "cherry"]));
+
+        let mut empty = List::new() as List<i32>;
+        assert!(empty.dedup().is_empty());
+    }
+
+    #[test]
+    fn list_drain() {
+        let mut list: List<i32> = (0..8).collect();
+        assert_eq!(list.drain(2..5).collect::<Vec<i32>>(), vec![2, 3, 4]);
+        assert_eq!(list, List::from([0, 1, 5, 6, 7]));
+        assert_eq!(list.drain(3..).collect::<Vec<i32>>(), vec![6, 7]);
+        list.append(9);
+        assert_eq!(list, List::from([0, 1, 5, 9]));
+        assert_eq!(list.drain(..=0).len(), 1);
+        assert_eq!(list.drain(1..1).count(), 0);
+        assert_eq!(list.drain(..).collect::<Vec<i32>>(), vec![1, 5, 9]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn list_drain_out_of_range() {
+        let mut list = List::from([0, 1, 2]);
+        list.drain(1..4);
+    }
+
+    #[test]
+    fn list_extract_if() {
+        let mut list: List<i32> = (0..10).collect();
+        let evens: Vec<i32> = list.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, vec![0, 2, 4, 6, 8]);
+        assert_eq!(list, List::from([1, 3, 5, 7, 9]));
+        list.append(11);
+        assert_eq!(list.size(), 6);
+
+        // stopping early leaves the rest in place
+        let first = list.extract_if(|x| *x > 2).next();
+        assert_eq!(first, Some(3));
+        assert_eq!(list, List::from([1, 5, 7, 9, 11]));
+    }
+
+    #[test]
+    fn list_pop_front() {
+        let mut list = List::from([1, 2]);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        list.append(3);
+        assert_eq!(list, List::from([3]));
+    }
+
+    #[test]
+    fn list_get() {
+        let mut list = List::from([1, 2, 3]);
+        assert_eq!(list.get(1), Some(&2));
+        assert_eq!(list.get(3), None);
+        *list.get_mut(2).unwrap() = 30;
+        assert_eq!(list.get_mut(3), None);
+        assert_eq!(list, List::from([1, 2, 30]));
+    }
+
+    #[test]
+    fn list_first_last() {
+        let mut list = List::new();
+        assert_eq!(list.first(), None);
+        assert_eq!(list.last(), None);
+        list.append(1).append(2).prepend(0);
+        assert_eq!(list.first(), Some(&0));
+        assert_eq!(list.last(), Some(&2));
+    }
+
+    #[test]
+    fn list_pop_back() {
+        let mut list = List::from([1, 2, 3]);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.last(), Some(&2));
+        list.append(4);
+        assert_eq!(list, List::from([1, 2, 4]));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn list_take_at() {
+        let mut list = List::from([String::from("a"), String::from("b"), String::from("c")]);
+        assert_eq!(list.take_at(1), Some(String::from("b")));
+        assert_eq!(list.take_at(5), None);
+        assert_eq!(list.try_take_at(2), Err(Error::IndexOutOfBounds { index: 2, len: 2 }));
+        assert_eq!(list.try_take_at(1), Ok(String::from("c")));
+        assert_eq!(list.last(), Some(&String::from("a")));
+        assert_eq!(list.take_at(0), Some(String::from("a")));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn list_swap() {
+        let mut list = List::from([0, 1, 2, 3]);
+        list.swap(0, 3).swap(1, 1).swap(1, 10);
+        assert_eq!(list, List::from([3, 1, 2, 0]));
+        assert_eq!(list.try_swap(4, 0).err(), Some(Error::IndexOutOfBounds { index: 4, len: 4 }));
+        assert_eq!(list.last(), Some(&0));
+    }
+
+    #[test]
+    fn list_replace() {
+        let mut list = List::from([0, 1, 2]);
+        assert_eq!(list.replace(1, 10), 1);
+        assert_eq!(list.try_replace(2, 20), Ok(2));
+        assert_eq!(list.try_replace(3, 30), Err((Error::IndexOutOfBounds { index: 3, len: 3 }, 30)));
+        assert_eq!(list, List::from([0, 10, 20]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn list_replace_out_of_bounds() {
+        List::from([0]).replace(1, 1);
+    }
+
+    #[test]
+    fn list_predicate_search() {
+        struct Record {
+            id: u32,
+            name: &'static str
+        }
+
+        let list = List::from([
+            Record { id: 1, name: "a" },
+            Record { id: 2, name: "b" },
+            Record { id: 3, name: "a" },
+        ]);
+        assert_eq!(list.find(|r| r.name == "a").map(|r| r.id), Some(1));
+        assert!(list.find(|r| r.id > 3).is_none());
+        assert_eq!(list.find_map(|r| if r.id > 1 { Some(r.name) } else { None }), Some("b"));
+        assert_eq!(list.position(|r| r.name == "a"), Some(0));
+        assert_eq!(list.rposition(|r| r.name == "a"), Some(2));
+        assert_eq!(list.rposition(|r| r.name == "z"), None);
+        assert!(list.contains_by(|r| r.id == 2));
+        assert!(!list.contains_by(|r| r.id == 4));
+        assert_eq!(list.count_by(|r| r.name == "a"), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn list_index_of_all() {
+        let list = List::from([1, 2, 1, 3, 1]);
+        assert_eq!(list.index_of_all(&1), vec![0, 2, 4]);
+        assert_eq!(list.index_of_all(&5), vec![]);
+    }
+}
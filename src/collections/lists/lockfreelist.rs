@@ -0,0 +1,469 @@
+use crate::epoch::{Collector, Guard};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering as AtomicOrdering};
+use std::vec;
+
+struct LockFreeNode<T> {
+    element: T,
+    next: AtomicPtr<LockFreeNode<T>> // the lowest bit is set once this node is logically removed
+}
+
+// nodes are at least pointer aligned, so the lowest bit of a pointer to one is free to carry the removed mark
+fn marked<T>(ptr: *mut LockFreeNode<T>) -> *mut LockFreeNode<T> {
+    ptr.map_addr(|addr| addr | 1)
+}
+
+fn unmarked<T>(ptr: *mut LockFreeNode<T>) -> *mut LockFreeNode<T> {
+    ptr.map_addr(|addr| addr & !1)
+}
+
+fn is_marked<T>(ptr: *mut LockFreeNode<T>) -> bool {
+    ptr.addr() & 1 == 1
+}
+
+// sorted set on a singly linked list that threads edit without ever taking a lock (Harris and Michael).
+// removing is done in two steps: first the next pointer of the node is marked, which stops anyone from linking
+// behind it and removes it logically, then it is unlinked by whichever thread gets there first. unlinked nodes are
+// handed to an epoch collector and freed once no thread that may still be reading them is left.
+pub struct LockFreeList<T> {
+    head: AtomicPtr<LockFreeNode<T>>, // first node of the list, never marked itself
+    count: AtomicUsize, // the amount of elements in the list
+    collector: Collector<LockFreeNode<T>>,
+    marker: PhantomData<Box<LockFreeNode<T>>> // the list owns its nodes
+}
+
+// SAFETY: elements are moved in and dropped by whichever thread frees their node and are shared by reference
+// between all threads using the list, which is exactly what Send and Sync on T allow
+unsafe impl<T: Send> Send for LockFreeList<T> {}
+unsafe impl<T: Send + Sync> Sync for LockFreeList<T> {}
+
+impl<T> LockFreeList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            count: AtomicUsize::new(0),
+            collector: Collector::new(),
+            marker: PhantomData
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.count.load(AtomicOrdering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    // calls f with every element that is not removed, in order, while the nodes are protected by the guard
+    fn for_each<F: FnMut(&T)>(&self, mut f: F) {
+        let _guard = self.collector.pin();
+        let mut curr = self.head.load(AtomicOrdering::SeqCst);
+        while !curr.is_null() {
+            // SAFETY: we are pinned, so no node reachable from the head is freed before the guard is dropped
+            let node = unsafe { &*curr };
+            let next = node.next.load(AtomicOrdering::SeqCst);
+            if !is_marked(next) {
+                f(&node.element);
+            }
+            curr = unmarked(next);
+        }
+    }
+}
+
+impl<T: Ord> LockFreeList<T> {
+    // adds element at its sorted position, false when it was already in the set
+    pub fn insert(&self, element: T) -> bool {
+        let guard = self.collector.pin();
+        let node = Box::into_raw(Box::new(LockFreeNode {
+            element,
+            next: AtomicPtr::new(ptr::null_mut())
+        }));
+
+        loop {
+            // SAFETY: the new node is ours until the compare exchange below publishes it
+            let (prev, curr, found) = self.find(unsafe { &(*node).element }, &guard);
+            if found {
+                drop(unsafe { Box::from_raw(node) });
+                return false;
+            }
+
+            unsafe { (*node).next.store(curr, AtomicOrdering::SeqCst) };
+            if prev.compare_exchange(curr, node, AtomicOrdering::SeqCst, AtomicOrdering::SeqCst).is_ok() {
+                self.count.fetch_add(1, AtomicOrdering::SeqCst);
+                return true;
+            }
+        }
+    }
+
+    // takes element out of the set, false when it was not in it
+    pub fn remove(&self, element: &T) -> bool {
+        let guard = self.collector.pin();
+        loop {
+            let (prev, curr, found) = self.find(element, &guard);
+            if !found {
+                return false;
+            }
+
+            // SAFETY: curr was reachable while we are pinned, so it is not freed before the guard is dropped
+            let node = unsafe { &*curr };
+            let next = node.next.load(AtomicOrdering::SeqCst);
+            if is_marked(next) {
+                continue; // another thread is removing it, find will finish the job or tell us it is gone
+            }
+
+            // marking the node is what removes it, only one thread can win this
+            if node.next.compare_exchange(next, marked(next), AtomicOrdering::SeqCst, AtomicOrdering::SeqCst).is_err() {
+                continue;
+            }
+            self.count.fetch_sub(1, AtomicOrdering::SeqCst);
+
+            // try to unlink it straight away, if the predecessor changed meanwhile a search cleans it up
+            if prev.compare_exchange(curr, next, AtomicOrdering::SeqCst, AtomicOrdering::SeqCst).is_ok() {
+                unsafe { self.collector.retire(curr) };
+            } else {
+                self.find(element, &guard);
+            }
+            return true;
+        }
+    }
+
+    // never writes to the list, so readers do not slow each other or the writers down
+    pub fn contains(&self, element: &T) -> bool {
+        let _guard = self.collector.pin();
+        let mut curr = self.head.load(AtomicOrdering::SeqCst);
+        while !curr.is_null() {
+            // SAFETY: we are pinned, so no node reachable from the head is freed before the guard is dropped
+            let node = unsafe { &*curr };
+            let next = node.next.load(AtomicOrdering::SeqCst);
+            match node.element.cmp(element) {
+                Ordering::Less => curr = unmarked(next),
+                Ordering::Equal => return !is_marked(next),
+                Ordering::Greater => return false,
+            }
+        }
+        false
+    }
+
+    // returns the link that points at the first node not smaller than element, that node and whether it holds
+    // element. marked nodes on the way are unlinked, if that fails the search starts over from the head.
+    fn find<'g>(&'g self, element: &T, _guard: &'g Guard<'_, LockFreeNode<T>>)
+                -> (&'g AtomicPtr<LockFreeNode<T>>, *mut LockFreeNode<T>, bool) {
+        'retry: loop {
+            let mut prev = &self.head;
+            let mut curr = prev.load(AtomicOrdering::SeqCst);
+            loop {
+                if curr.is_null() {
+                    return (prev, curr, false);
+                }
+
+                // SAFETY: the guard keeps every node we can still reach alive for 'g
+                let node = unsafe { &*curr };
+                let next = node.next.load(AtomicOrdering::SeqCst);
+                if is_marked(next) {
+                    let next = unmarked(next);
+                    if prev.compare_exchange(curr, next, AtomicOrdering::SeqCst, AtomicOrdering::SeqCst).is_err() {
+                        continue 'retry;
+                    }
+                    unsafe { self.collector.retire(curr) };
+                    curr = next;
+                    continue;
+                }
+
+                match node.element.cmp(element) {
+                    Ordering::Less => {
+                        prev = &node.next;
+                        curr = next;
+                    },
+                    Ordering::Equal => return (prev, curr, true),
+                    Ordering::Greater => return (prev, curr, false),
+                }
+            }
+        }
+    }
+}
+
+impl<T: Clone> LockFreeList<T> {
+    // iterates over a snapshot of the list, taken in one pass when the iterator is created
+    pub fn iter(&self) -> LockFreeListIterator<T> {
+        LockFreeListIterator {
+            elements: self.to_vec().into_iter()
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut elements = Vec::new();
+        self.for_each(|x| elements.push(x.clone()));
+        elements
+    }
+}
+
+impl<T> Drop for LockFreeList<T> {
+    fn drop(&mut self) {
+        // nobody else can reach the list anymore, free the linked nodes, the collector frees the unlinked ones
+        let mut curr = *self.head.get_mut();
+        while !curr.is_null() {
+            let mut node = unsafe { Box::from_raw(curr) };
+            curr = unmarked(*node.next.get_mut());
+        }
+    }
+}
+
+impl<T> Default for LockFreeList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for LockFreeList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for LockFreeList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LockFreeList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Ord> Extend<T> for LockFreeList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LockFreeList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut set = f.debug_set();
+        self.for_each(|x| {
+            set.entry(x);
+        });
+        set.finish()
+    }
+}
+
+// yields the elements the list held when the iterator was created, edits made afterwards are not seen
+pub struct LockFreeListIterator<T> {
+    elements: vec::IntoIter<T>
+}
+
+impl<T> Iterator for LockFreeListIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for LockFreeListIterator<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::LockFreeList;
+    use std::cmp::Ordering as CmpOrdering;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn lflist_new() {
+        let list = LockFreeList::new() as LockFreeList<i32>;
+        assert!(list.is_empty());
+        assert!(!list.contains(&1));
+        assert!(!list.remove(&1));
+        assert_eq!(list.to_vec(), vec![]);
+    }
+
+    #[test]
+    fn lflist_insert_keeps_order() {
+        let list = LockFreeList::new();
+        assert!(list.insert(3));
+        assert!(list.insert(1));
+        assert!(list.insert(2));
+        assert!(!list.insert(2));
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+        assert_eq!(format!("{:?}", list), "{1, 2, 3}");
+    }
+
+    #[test]
+    fn lflist_remove_and_contains() {
+        let list: LockFreeList<i32> = vec![5, 1, 4, 2, 3].into();
+        assert!(list.contains(&4));
+        assert!(list.remove(&4));
+        assert!(!list.remove(&4));
+        assert!(!list.contains(&4));
+        assert!(list.remove(&1));
+        assert!(list.remove(&5));
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![2, 3]);
+        assert_eq!(list.size(), 2);
+    }
+
+    #[test]
+    fn lflist_iter_is_a_snapshot() {
+        let list: LockFreeList<i32> = (0..5).collect();
+        let iter = list.iter();
+        list.remove(&0);
+        list.insert(10);
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.collect::<Vec<i32>>(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn lflist_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<LockFreeList<String>>();
+    }
+
+    #[test]
+    fn lflist_concurrent_inserters() {
+        // the threads insert overlapping ranges, every value must end up in the list exactly once
+        let list = Arc::new(LockFreeList::new());
+        let handles: Vec<_> = (0..4).map(|t| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                let mut inserted = 0;
+                for x in (t * 250)..(t * 250 + 1000) {
+                    if list.insert(x) {
+                        inserted += 1;
+                    }
+                }
+                inserted
+            })
+        }).collect();
+        let inserted: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        assert_eq!(inserted, 1750);
+        assert_eq!(list.size(), 1750);
+        assert_eq!(list.to_vec(), (0..1750).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn lflist_concurrent_inserters_and_removers() {
+        let list = Arc::new((0..2000).filter(|x| x % 2 == 0).collect::<LockFreeList<i32>>());
+        let mut handles = Vec::new();
+
+        // the removers take every even value and fight over the multiples of four
+        for t in 0..2 {
+            let list = Arc::clone(&list);
+            handles.push(thread::spawn(move || {
+                let mut removed = 0;
+                for x in (0..2000).step_by(2) {
+                    if (x % 4 == 0 || x % 4 == t * 2) && list.remove(&x) {
+                        removed += 1;
+                    }
+                }
+                removed
+            }));
+        }
+
+        // the inserters add every odd value, both of them try all of them
+        for _ in 0..2 {
+            let list = Arc::clone(&list);
+            handles.push(thread::spawn(move || {
+                for x in (1..2000).step_by(2) {
+                    list.insert(x);
+                }
+                0
+            }));
+        }
+
+        // readers only look, the values they ask for are never touched
+        for _ in 0..2 {
+            let list = Arc::clone(&list);
+            handles.push(thread::spawn(move || {
+                for _ in 0..200 {
+                    assert!(!list.contains(&5000));
+                    let snapshot = list.to_vec();
+                    assert!(snapshot.windows(2).all(|w| w[0] < w[1]));
+                }
+                0
+            }));
+        }
+
+        let removed: i32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(removed, 1000);
+        assert_eq!(list.size(), 1000);
+        assert_eq!(list.to_vec(), (1..2000).step_by(2).collect::<Vec<i32>>());
+    }
+
+    // counts how often it is dropped, so the test can tell the collector neither leaks nor frees twice
+    struct Tracked(i32, Arc<AtomicUsize>);
+
+    impl PartialEq for Tracked {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Tracked {}
+
+    impl PartialOrd for Tracked {
+        fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tracked {
+        fn cmp(&self, other: &Self) -> CmpOrdering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn lflist_concurrent_churn_frees_every_node_once() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let list = Arc::new(LockFreeList::new());
+        let handles: Vec<_> = (0..4).map(|_| {
+            let list = Arc::clone(&list);
+            let dropped = Arc::clone(&dropped);
+            thread::spawn(move || {
+                let mut created = 0;
+                for round in 0..20 {
+                    for x in 0..100 {
+                        list.insert(Tracked(x, Arc::clone(&dropped)));
+                        created += 1;
+                        if (x + round) % 3 == 0 {
+                            list.remove(&Tracked(x, Arc::clone(&dropped)));
+                            created += 1;
+                        }
+                    }
+                }
+                created
+            })
+        }).collect();
+        let created: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        let list = Arc::try_unwrap(list).ok().unwrap();
+        drop(list);
+        assert_eq!(dropped.load(Ordering::SeqCst), created);
+    }
+
+    #[test]
+    fn lflist_drop_long() {
+        let mut list = LockFreeList::new();
+        // inserting in descending order keeps every insert at the head
+        list.extend((0..100_000).rev());
+        assert_eq!(list.size(), 100_000);
+        drop(list);
+    }
+}
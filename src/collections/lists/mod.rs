@@ -1 +1,19 @@
-pub mod linkedlist;
\ No newline at end of file
+pub mod arenalist;
+pub mod circularlist;
+pub mod doublylinkedlist;
+pub mod linkedlist;
+pub mod lockfreelist;
+pub mod persistentlist;
+pub mod synclist;
+pub mod unrolledlist;
+
+pub use self::arenalist::{ArenaList, ArenaListCursor, ArenaListCursorMut, ArenaListIntoIterator, ArenaListIterator,
+                          ArenaListIteratorMut};
+pub use self::circularlist::{CircularList, CircularListIterator};
+pub use self::doublylinkedlist::{DoublyLinkedList, DoublyLinkedListIterator};
+pub use self::linkedlist::{Cursor, CursorMut, List, ListExtractIf, ListIntoIterator, ListIterator, ListIteratorMut};
+pub use self::lockfreelist::{LockFreeList, LockFreeListIterator};
+pub use self::persistentlist::{PersistentList, PersistentListIterator};
+pub use self::synclist::{SyncList, SyncListIterator};
+pub use self::unrolledlist::{UnrolledList, UnrolledListCursor, UnrolledListCursorMut, UnrolledListIntoIterator,
+                             UnrolledListIterator, UnrolledListIteratorMut};
@@ -0,0 +1,328 @@
+use crate::node::{Node, NodePtr, NodePtrOpt};
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+// immutable cons list, every operation returns a new list that shares as many nodes as possible with the old one.
+// a node is never modified once it is linked, so any number of versions can point at the same tail.
+pub struct PersistentList<T> {
+    head: NodePtrOpt<T>, // head node, the node at the beginning of the list
+    count: usize // the amount of elements in the list
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            count: 0
+        }
+    }
+
+    // O(1), the new list shares every node of this one
+    pub fn push_front(&self, element: T) -> PersistentList<T> {
+        let node = Node::new(element);
+        node.borrow_mut().set_next_node(self.head.as_ref().map(Rc::clone));
+
+        PersistentList {
+            head: Some(node),
+            count: self.count + 1
+        }
+    }
+
+    // O(1), the list without its first element, the tail of an empty list is empty
+    pub fn tail(&self) -> PersistentList<T> {
+        match &self.head {
+            Some(head) => PersistentList {
+                head: head.borrow().get_next(),
+                count: self.count - 1
+            },
+            None => PersistentList::new(),
+        }
+    }
+
+    // O(1), the first element together with the rest of the list
+    pub fn pop_front(&self) -> Option<(&T, PersistentList<T>)> {
+        let first = self.first()?;
+        Some((first, self.tail()))
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.head.as_ref().map(PersistentListIterator::element)
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn iter(&self) -> PersistentListIterator<'_, T> {
+        PersistentListIterator {
+            current: self.head.as_ref().map(Rc::clone),
+            len: self.count,
+            marker: PhantomData
+        }
+    }
+
+    // true when both lists are the very same version, i.e. they share their head node
+    pub fn ptr_eq(&self, other: &PersistentList<T>) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Clone> PersistentList<T> {
+    // the nodes of self are copied, other is shared as the tail of the result, so this is O(self.size())
+    pub fn concat(&self, other: &PersistentList<T>) -> PersistentList<T> {
+        let mut elements: Vec<&T> = self.iter().collect();
+        let mut list = other.clone();
+        while let Some(element) = elements.pop() {
+            list = list.push_front(element.clone());
+        }
+        list
+    }
+
+    // O(n), nothing can be shared as every node needs a new successor
+    pub fn reverse(&self) -> PersistentList<T> {
+        let mut list = PersistentList::new();
+        for element in self.iter() {
+            list = list.push_front(element.clone());
+        }
+        list
+    }
+}
+
+impl<T: PartialEq> PersistentList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+}
+
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        // free the nodes only this version holds one at a time, the first shared node belongs to another version
+        let mut cur = self.head.take();
+        while let Some(node) = cur {
+            cur = match Rc::try_unwrap(node) {
+                Ok(node) => {
+                    let mut node = node.into_inner();
+                    let next = node.get_next();
+                    node.set_next_node(None);
+                    next
+                },
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+// O(1), the copy shares every node
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        PersistentList {
+            head: self.head.as_ref().map(Rc::clone),
+            count: self.count
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for PersistentList<T> {
+    fn from(mut vec: Vec<T>) -> Self {
+        let mut list = PersistentList::new();
+        while let Some(element) = vec.pop() {
+            list = list.push_front(element);
+        }
+        list
+    }
+}
+
+impl<T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PersistentList::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: Eq> Eq for PersistentList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct PersistentListIterator<'a, T> {
+    current: NodePtrOpt<T>,
+    len: usize, // elements left to yield
+    marker: PhantomData<&'a T> // the iterator borrows from the list it was created from
+}
+
+impl<'a, T> PersistentListIterator<'a, T> {
+    // SAFETY: the node is kept alive by the list, which is borrowed for 'a, and linked nodes are never borrowed
+    // mutably again (only a node no list holds anymore is taken apart in drop), so the reference stays valid
+    fn element(node: &NodePtr<T>) -> &'a T {
+        unsafe { &(*node.as_ptr()).element }
+    }
+}
+
+impl<'a, T> Iterator for PersistentListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = node.borrow().get_next();
+        self.len -= 1;
+        Some(PersistentListIterator::element(&node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PersistentListIterator<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::PersistentList;
+    use std::rc::Rc;
+
+    #[test]
+    fn plist_new() {
+        let list = PersistentList::new() as PersistentList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.first(), None);
+        assert!(list.tail().is_empty());
+        assert!(list.pop_front().is_none());
+    }
+
+    #[test]
+    fn plist_push_front() {
+        let empty = PersistentList::new();
+        let one = empty.push_front(1);
+        let two = one.push_front(2);
+        assert!(empty.is_empty());
+        assert_eq!(one, PersistentList::from(vec![1]));
+        assert_eq!(two, PersistentList::from(vec![2, 1]));
+        assert_eq!(two.size(), 2);
+    }
+
+    #[test]
+    fn plist_shares_tails() {
+        let base = PersistentList::from(vec![2, 3]);
+        let a = base.push_front(1);
+        let b = base.push_front(10);
+        assert!(a.tail().ptr_eq(&base));
+        assert!(b.tail().ptr_eq(&base));
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(a, PersistentList::from(vec![1, 2, 3]));
+        assert_eq!(b, PersistentList::from(vec![10, 2, 3]));
+    }
+
+    #[test]
+    fn plist_pop_front() {
+        let list = PersistentList::from(vec![String::from("a"), String::from("b")]);
+        let (first, rest) = list.pop_front().unwrap();
+        assert_eq!(first, "a");
+        assert_eq!(rest, PersistentList::from(vec![String::from("b")]));
+        assert_eq!(list.size(), 2);
+        let (second, rest) = rest.pop_front().unwrap();
+        assert_eq!(second, "b");
+        assert!(rest.pop_front().is_none());
+    }
+
+    #[test]
+    fn plist_concat() {
+        let front = PersistentList::from(vec![1, 2]);
+        let back = PersistentList::from(vec![3, 4]);
+        let both = front.concat(&back);
+        assert_eq!(both, PersistentList::from(vec![1, 2, 3, 4]));
+        assert!(both.tail().tail().ptr_eq(&back));
+        assert_eq!(front, PersistentList::from(vec![1, 2]));
+        assert!(PersistentList::new().concat(&back).ptr_eq(&back));
+    }
+
+    #[test]
+    fn plist_reverse_and_search() {
+        let list: PersistentList<i32> = (1..=4).collect();
+        assert_eq!(list.reverse(), PersistentList::from(vec![4, 3, 2, 1]));
+        assert_eq!(list.index(&3), Some(2));
+        assert_eq!(list.count(&3), 1);
+        assert!(list.has(&4));
+        assert!(!list.has(&5));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4]");
+    }
+
+    #[test]
+    fn plist_undo_history() {
+        let mut history = vec![PersistentList::new()];
+        for x in 0..500 {
+            let next = history.last().unwrap().push_front(x);
+            history.push(next);
+        }
+
+        // every version is intact and is only one new node in front of the previous version
+        for (version, list) in history.iter().enumerate() {
+            assert_eq!(list.size(), version);
+            assert_eq!(list.first().copied(), version.checked_sub(1));
+            if version > 0 {
+                assert!(list.tail().ptr_eq(&history[version - 1]));
+            }
+        }
+    }
+
+    #[test]
+    fn plist_drop_frees_unshared_nodes() {
+        let tracker = Rc::new(());
+        let base = PersistentList::new().push_front(Rc::clone(&tracker));
+        let version = base.push_front(Rc::clone(&tracker)).push_front(Rc::clone(&tracker));
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        drop(version);
+        assert_eq!(Rc::strong_count(&tracker), 2);
+        drop(base);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn plist_drop_long() {
+        let mut list = PersistentList::new();
+        for x in 0..1_000_000 {
+            list = list.push_front(x);
+        }
+        drop(list);
+    }
+}
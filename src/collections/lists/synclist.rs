@@ -0,0 +1,637 @@
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+type SyncNodePtr<T> = Arc<Mutex<SyncNode<T>>>;
+type SyncNodePtrOpt<T> = Option<SyncNodePtr<T>>;
+
+struct SyncNode<T> {
+    element: Option<T>, // none for the sentinel in front of the list and for removed nodes
+    next: SyncNodePtrOpt<T>,
+    removed: bool // set once the node is unlinked, other threads may still be holding on to it
+}
+
+impl<T> SyncNode<T> {
+    fn new(element: Option<T>) -> SyncNodePtr<T> {
+        Arc::new(Mutex::new(Self {
+            element,
+            next: None,
+            removed: false
+        }))
+    }
+}
+
+// every update to a node is a plain assignment, so a panic while a node was locked never leaves it half updated
+fn lock<T>(node: &SyncNodePtr<T>) -> MutexGuard<'_, SyncNode<T>> {
+    node.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn next_of<T>(node: &SyncNodePtr<T>) -> SyncNodePtrOpt<T> {
+    lock(node).next.as_ref().map(Arc::clone)
+}
+
+// singly linked list that can be shared between threads. every node has its own lock, traversals lock one node at
+// a time and edits lock the node before the change and the node being changed (always in list order, so two
+// threads never wait on each other in a cycle), then check the two are still linked before touching them.
+pub struct SyncList<T> {
+    head: SyncNodePtr<T>, // sentinel node, its next node is the first element of the list
+    count: AtomicUsize // the amount of elements in the list
+}
+
+impl<T> SyncList<T> {
+    pub fn new() -> Self {
+        Self {
+            head: SyncNode::new(None),
+            count: AtomicUsize::new(0)
+        }
+    }
+
+    // adds element to beginning of list
+    pub fn prepend(&self, element: T) -> &Self {
+        self.insert(0, element)
+    }
+
+    // add element to the end of the list, the list has no tail pointer so this walks the whole list
+    pub fn append(&self, element: T) -> &Self {
+        self.append_chain(SyncNode::new(Some(element)), 1);
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.count.load(AtomicOrdering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    pub fn insert(&self, index: usize, element: T) -> &Self {
+        let _ = self.try_insert(index, element);
+        self
+    }
+
+    // like insert, but reports an out of range index instead of ignoring it
+    pub fn try_insert(&self, index: usize, element: T) -> Result<&Self> {
+        let node = SyncNode::new(Some(element));
+
+        loop {
+            let prev = match self.walk(index) {
+                Some(prev) => prev,
+                None => return Err(Error::IndexOutOfBounds { index, len: self.size() }),
+            };
+
+            let mut prev_guard = lock(&prev);
+            if prev_guard.removed {
+                continue; // lost a race with a remover, start over
+            }
+
+            lock(&node).next = prev_guard.next.take();
+            prev_guard.next = Some(node);
+            self.count.fetch_add(1, AtomicOrdering::SeqCst);
+            return Ok(self);
+        }
+    }
+
+    pub fn remove_at(&self, index: usize) -> &Self {
+        let _ = self.try_remove_at(index);
+        self
+    }
+
+    // like remove_at, but reports an out of range index instead of ignoring it
+    pub fn try_remove_at(&self, index: usize) -> Result<&Self> {
+        self.unlink_at(index)?;
+        Ok(self)
+    }
+
+    pub fn pop_front(&self) -> Option<T> {
+        self.unlink_at(0).ok()
+    }
+
+    pub fn clear(&self) -> &Self {
+        while self.unlink_at(0).is_ok() {}
+        self
+    }
+
+    // needs exclusive access, every link in the list changes
+    pub fn reverse(&mut self) -> &mut Self {
+        let mut prev = None;
+        let mut cur = lock(&self.head).next.take();
+        while let Some(node) = cur {
+            let mut guard = lock(&node);
+            cur = guard.next.take();
+            guard.next = prev;
+            drop(guard);
+            prev = Some(node);
+        }
+
+        lock(&self.head).next = prev;
+        self
+    }
+
+    // stable sort, needs exclusive access. the elements stay in their nodes, the nodes are sorted and only relinked
+    // once the sort is done, so a panicking compare leaves the list in its old order
+    pub fn sort_by<F>(&mut self, mut compare: F) -> &mut Self
+        where F: FnMut(&T, &T) -> Ordering {
+        let mut nodes = Vec::with_capacity(self.size());
+        let mut cur = next_of(&self.head);
+        while let Some(node) = cur {
+            cur = next_of(&node);
+            nodes.push(node);
+        }
+
+        nodes.sort_by(|a, b| {
+            // a node compared with itself would be locked twice
+            if Arc::ptr_eq(a, b) {
+                return Ordering::Equal;
+            }
+            let (a, b) = (lock(a), lock(b));
+            compare(a.element.as_ref().unwrap(), b.element.as_ref().unwrap())
+        });
+
+        let mut next = None;
+        for node in nodes.into_iter().rev() {
+            lock(&node).next = next;
+            next = Some(node);
+        }
+        lock(&self.head).next = next;
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &mut Self
+        where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // calls f on every element front to back, each node is locked while f looks at it. f must not lock nodes of
+    // this list itself, so no edits, searches or iteration of it from inside f or that deadlocks on the current
+    // node. size and is_empty are fine, and other threads may keep working on the list meanwhile
+    pub fn for_each<F>(&self, mut f: F)
+        where F: FnMut(&T) {
+        let mut cur = next_of(&self.head);
+        while let Some(node) = cur {
+            let guard = lock(&node);
+            if let Some(element) = &guard.element {
+                f(element);
+            }
+            cur = guard.next.as_ref().map(Arc::clone);
+        }
+    }
+
+    // walks steps nodes past the sentinel, none when the list is too short
+    fn walk(&self, steps: usize) -> SyncNodePtrOpt<T> {
+        let mut node = Arc::clone(&self.head);
+        for _ in 0..steps {
+            node = next_of(&node)?;
+        }
+        Some(node)
+    }
+
+    // links a chain of len fresh nodes starting at head in after the last node
+    fn append_chain(&self, head: SyncNodePtr<T>, len: usize) {
+        let mut head = Some(head);
+        loop {
+            let mut last = Arc::clone(&self.head);
+            while let Some(next) = next_of(&last) {
+                last = next;
+            }
+
+            let mut last_guard = lock(&last);
+            if last_guard.removed || last_guard.next.is_some() {
+                continue; // lost a race with another writer, start over
+            }
+
+            last_guard.next = head.take();
+            self.count.fetch_add(len, AtomicOrdering::SeqCst);
+            return;
+        }
+    }
+
+    // unlinks curr from behind prev, none when the two are no longer linked to each other
+    fn try_unlink(&self, prev: &SyncNodePtr<T>, curr: &SyncNodePtr<T>) -> Option<T> {
+        let mut prev_guard = lock(prev);
+        let mut curr_guard = lock(curr);
+        let linked = match &prev_guard.next {
+            Some(next) => Arc::ptr_eq(next, curr),
+            None => false,
+        };
+
+        if prev_guard.removed || curr_guard.removed || !linked {
+            return None;
+        }
+
+        // curr keeps its next link so threads currently standing on it can still walk on
+        curr_guard.removed = true;
+        prev_guard.next = curr_guard.next.as_ref().map(Arc::clone);
+        self.count.fetch_sub(1, AtomicOrdering::SeqCst);
+        curr_guard.element.take()
+    }
+
+    fn unlink_at(&self, index: usize) -> Result<T> {
+        loop {
+            let prev = self.walk(index);
+            let curr = prev.as_ref().and_then(next_of);
+            let (prev, curr) = match (prev, curr) {
+                (Some(prev), Some(curr)) => (prev, curr),
+                _ => return Err(Error::IndexOutOfBounds { index, len: self.size() }),
+            };
+
+            if let Some(element) = self.try_unlink(&prev, &curr) {
+                return Ok(element);
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> SyncList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        let mut count = 0;
+        self.for_each(|x| if x == element {
+            count += 1;
+        });
+        count
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        let mut i = 0;
+        let mut found = None;
+        self.for_each(|x| {
+            if found.is_none() && x == element {
+                found = Some(i);
+            }
+            i += 1;
+        });
+        found
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.index(element).is_some()
+    }
+
+    // removes the first occurrence of element
+    pub fn remove(&self, element: &T) -> &Self {
+        let _ = self.try_remove(element);
+        self
+    }
+
+    // like remove, but reports when the element is not in the list
+    pub fn try_remove(&self, element: &T) -> Result<&Self> {
+        'retry: loop {
+            let mut prev = Arc::clone(&self.head);
+            loop {
+                let curr = match next_of(&prev) {
+                    Some(curr) => curr,
+                    None => return Err(Error::NotFound),
+                };
+
+                let matches = lock(&curr).element.as_ref() == Some(element);
+                if matches {
+                    match self.try_unlink(&prev, &curr) {
+                        Some(_) => return Ok(self),
+                        None => continue 'retry, // lost a race with another writer, start over
+                    }
+                }
+
+                prev = curr;
+            }
+        }
+    }
+}
+
+impl<T: Clone> SyncList<T> {
+    // snapshot of the elements, see SyncListIterator
+    pub fn iter(&self) -> SyncListIterator<'_, T> {
+        SyncListIterator {
+            current: next_of(&self.head),
+            marker: PhantomData
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+impl<T: Ord> SyncList<T> {
+    pub fn sort(&mut self) -> &mut Self {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+}
+
+impl<T> Drop for SyncList<T> {
+    fn drop(&mut self) {
+        // unlink the nodes one at a time, dropping the head would otherwise drop every following node recursively
+        let mut cur = lock(&self.head).next.take();
+        while let Some(node) = cur {
+            cur = match Arc::try_unwrap(node) {
+                Ok(node) => node.into_inner().unwrap_or_else(PoisonError::into_inner).next,
+                Err(_) => None, // someone else still holds the rest of the chain and will drop it
+            };
+        }
+    }
+}
+
+impl<T> Default for SyncList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for SyncList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut list = SyncList::new();
+        list.extend(vec);
+        list
+    }
+}
+
+impl<T> FromIterator<T> for SyncList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SyncList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for SyncList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        // build the new nodes into a chain first, so the end of the list is only searched for once
+        let mut iter = iter.into_iter();
+        let head = match iter.next() {
+            Some(element) => SyncNode::new(Some(element)),
+            None => return,
+        };
+
+        let mut tail = Arc::clone(&head);
+        let mut len = 1;
+        for element in iter {
+            let node = SyncNode::new(Some(element));
+            lock(&tail).next = Some(Arc::clone(&node));
+            tail = node;
+            len += 1;
+        }
+
+        self.append_chain(head, len);
+    }
+}
+
+impl<T: PartialEq> PartialEq for SyncList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut a = next_of(&self.head);
+        let mut b = next_of(&other.head);
+        loop {
+            match (a, b) {
+                (Some(x), Some(y)) => {
+                    // lock the pair in address order, so two threads comparing the same lists both ways
+                    // cannot end up waiting on each other
+                    let equal = match Arc::as_ptr(&x).cmp(&Arc::as_ptr(&y)) {
+                        Ordering::Less => lock(&x).element == lock(&y).element,
+                        Ordering::Greater => {
+                            let y_guard = lock(&y);
+                            lock(&x).element == y_guard.element
+                        },
+                        Ordering::Equal => true,
+                    };
+                    if !equal {
+                        return false;
+                    }
+                    a = next_of(&x);
+                    b = next_of(&y);
+                },
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SyncList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        self.for_each(|x| {
+            list.entry(x);
+        });
+        list.finish()
+    }
+}
+
+// yields clones of the elements. other threads may edit the list meanwhile, the iterator sees every element that
+// stays in the list for the whole iteration and may or may not see the ones added or removed while it runs
+pub struct SyncListIterator<'a, T> {
+    current: SyncNodePtrOpt<T>,
+    marker: PhantomData<&'a SyncList<T>> // the iterator borrows the list it was created from
+}
+
+impl<'a, T: Clone> Iterator for SyncListIterator<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.current.take()?;
+            let guard = lock(&node);
+            self.current = guard.next.as_ref().map(Arc::clone);
+            if let Some(element) = &guard.element {
+                return Some(element.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncList;
+    use crate::error::Error;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn slist_new() {
+        let list = SyncList::new() as SyncList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.to_vec(), vec![]);
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn slist_prepend_append() {
+        let list = SyncList::new();
+        list.append(1).prepend(0).append(2);
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.to_vec(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn slist_insert() {
+        let list = SyncList::from(vec![0, 1, 3]);
+        list.insert(2, 2).insert(4, 4).insert(10, 10);
+        assert_eq!(list, SyncList::from(vec![0, 1, 2, 3, 4]));
+        assert_eq!(list.try_insert(6, 6).err(), Some(Error::IndexOutOfBounds { index: 6, len: 5 }));
+    }
+
+    #[test]
+    fn slist_remove() {
+        let list = SyncList::from(vec![0, 6, 1, 2, 1]);
+        list.remove(&2).remove(&0).remove(&1);
+        assert_eq!(list.to_vec(), vec![6, 1]);
+        assert_eq!(list.try_remove(&7).err(), Some(Error::NotFound));
+        assert_eq!(list.size(), 2);
+    }
+
+    #[test]
+    fn slist_remove_at() {
+        let list = SyncList::from(vec![0, 1, 2, 3]);
+        list.remove_at(2).remove_at(0);
+        assert_eq!(list.to_vec(), vec![1, 3]);
+        assert_eq!(list.try_remove_at(2).err(), Some(Error::IndexOutOfBounds { index: 2, len: 2 }));
+        assert_eq!(list.pop_front(), Some(1));
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn slist_search() {
+        let list = SyncList::from(vec![1, 2, 3, 2]);
+        assert_eq!(list.count(&2), 2);
+        assert_eq!(list.index(&3), Some(2));
+        assert!(list.has(&1));
+        assert!(!list.has(&100));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 2]");
+    }
+
+    #[test]
+    fn slist_reverse_sort() {
+        let mut list: SyncList<i32> = vec![3, 1, 2].into_iter().collect();
+        list.reverse();
+        assert_eq!(list.to_vec(), vec![2, 1, 3]);
+        list.sort().append(4);
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+        list.sort_by_key(|x| -x);
+        assert_eq!(list.to_vec(), vec![4, 3, 2, 1]);
+        list.extend(vec![0, -1]);
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![4, 3, 2, 1, 0, -1]);
+    }
+
+    #[test]
+    fn slist_sort_panicking_compare() {
+        let mut list: SyncList<i32> = vec![3, 1, 2].into_iter().collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|_, _| panic!("compare failed"));
+        }));
+        assert!(result.is_err());
+
+        // every element is still in place and the list keeps working
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.to_vec(), vec![3, 1, 2]);
+        list.sort();
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn slist_for_each() {
+        let list: SyncList<i32> = vec![1, 2, 3].into_iter().collect();
+        let doubled = SyncList::new();
+        let mut sum = 0;
+        list.for_each(|x| {
+            // the size of the list itself and other lists are fine to use from inside
+            sum += x * list.size() as i32;
+            doubled.append(x * 2);
+        });
+        assert_eq!(sum, 18);
+        assert_eq!(doubled.to_vec(), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn slist_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SyncList<String>>();
+    }
+
+    #[test]
+    fn slist_concurrent_inserters() {
+        let list = Arc::new(SyncList::new());
+        let handles: Vec<_> = (0..4).map(|t| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                for x in 0..500 {
+                    if x % 2 == 0 {
+                        list.append(t * 1000 + x);
+                    } else {
+                        list.prepend(t * 1000 + x);
+                    }
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut contents = list.to_vec();
+        contents.sort();
+        let mut expected: Vec<i32> = (0..4).flat_map(|t| (0..500).map(move |x| t * 1000 + x)).collect();
+        expected.sort();
+        assert_eq!(list.size(), 2000);
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn slist_concurrent_inserters_and_removers() {
+        // the removers take away every value the list starts with while the inserters add new ones
+        let list = Arc::new((0..2000).collect::<SyncList<i32>>());
+        let mut handles = Vec::new();
+        for t in 0..2 {
+            let list = Arc::clone(&list);
+            handles.push(thread::spawn(move || {
+                for x in (t..2000).step_by(2) {
+                    list.remove(&x);
+                }
+            }));
+        }
+        for t in 0..2 {
+            let list = Arc::clone(&list);
+            handles.push(thread::spawn(move || {
+                for x in 0..1000 {
+                    list.insert(x % 7, (10_000 + t * 1000 + x) as i32);
+                }
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut contents = list.to_vec();
+        contents.sort();
+        let expected: Vec<i32> = (10_000..12_000).collect();
+        assert_eq!(list.size(), 2000);
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn slist_concurrent_pop_front() {
+        let list = Arc::new((0..4000).collect::<SyncList<i32>>());
+        let handles: Vec<_> = (0..4).map(|_| {
+            let list = Arc::clone(&list);
+            thread::spawn(move || {
+                let mut popped = Vec::new();
+                while let Some(x) = list.pop_front() {
+                    popped.push(x);
+                }
+                popped
+            })
+        }).collect();
+
+        let mut all: Vec<i32> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+        all.sort();
+        assert_eq!(all, (0..4000).collect::<Vec<i32>>());
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn slist_drop_long() {
+        let list: SyncList<u32> = (0..1_000_000).collect();
+        drop(list);
+    }
+}
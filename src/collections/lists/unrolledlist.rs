@@ -0,0 +1,1189 @@
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Index;
+use std::slice;
+
+const CHUNK_CAPACITY: usize = 16; // elements per chunk, a full chunk is split in two halves
+
+// a node of the list, holding up to CHUNK_CAPACITY elements next to each other
+struct Chunk<T> {
+    elements: Vec<T>, // never empty while the chunk is linked
+    next: Option<usize>
+}
+
+// singly linked list of small arrays, stored in a slab like ArenaList. a scan touches one link per chunk instead
+// of one per element, and inserting or removing only shifts the elements of a single chunk.
+pub struct UnrolledList<T> {
+    chunks: Vec<Chunk<T>>, // the slab, chunks are never moved while linked
+    head: Option<usize>, // slot of the chunk at the beginning of the list
+    tail: Option<usize>, // slot of the chunk at the end of the list
+    free: Vec<usize>, // vacant slots, reused before the slab grows
+    count: usize // the amount of elements in the list
+}
+
+impl<T> UnrolledList<T> {
+    pub fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            head: None,
+            tail: None,
+            free: Vec::new(),
+            count: 0
+        }
+    }
+
+    // adds element to beginning of list
+    pub fn prepend(&mut self, element: T) -> &mut Self {
+        self.insert(0, element)
+    }
+
+    // add element to the end of the list
+    pub fn append(&mut self, element: T) -> &mut Self {
+        let count = self.count;
+        self.insert(count, element)
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn iter(&self) -> UnrolledListIterator<'_, T> {
+        UnrolledListIterator {
+            list: self,
+            current: self.head,
+            elements: [].iter(),
+            len: self.count
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> UnrolledListIteratorMut<'_, T> {
+        UnrolledListIteratorMut {
+            chunks: self.chunks.as_mut_ptr(),
+            current: self.head,
+            elements: [].iter_mut(),
+            len: self.count,
+            marker: PhantomData
+        }
+    }
+
+    // applies f to every element in place, front to back
+    pub fn for_each_mut<F>(&mut self, f: F) -> &mut Self
+        where F: FnMut(&mut T) {
+        self.iter_mut().for_each(f);
+        self
+    }
+
+    pub fn insert(&mut self, index: usize, element: T) -> &mut Self {
+        if index > self.count {
+            return self;
+        }
+
+        let (chunk, offset) = match (self.head, self.tail) {
+            (Some(head), Some(tail)) => {
+                if index == 0 && self.is_full(head) {
+                    // a new chunk in front, so a run of prepends fills whole chunks
+                    let chunk = self.alloc(Vec::with_capacity(CHUNK_CAPACITY), Some(head));
+                    self.head = Some(chunk);
+                    (chunk, 0)
+                } else if index == self.count {
+                    // a new chunk at the end, so a run of appends fills whole chunks
+                    if self.is_full(tail) {
+                        let chunk = self.alloc(Vec::with_capacity(CHUNK_CAPACITY), None);
+                        self.chunks[tail].next = Some(chunk);
+                        self.tail = Some(chunk);
+                        (chunk, 0)
+                    } else {
+                        (tail, self.chunks[tail].elements.len())
+                    }
+                } else {
+                    let (_, chunk, offset) = self.locate(index);
+                    if self.is_full(chunk) {
+                        self.split(chunk, offset)
+                    } else {
+                        (chunk, offset)
+                    }
+                }
+            },
+            _ => {
+                let chunk = self.alloc(Vec::with_capacity(CHUNK_CAPACITY), None);
+                self.head = Some(chunk);
+                self.tail = Some(chunk);
+                (chunk, 0)
+            },
+        };
+
+        self.chunks[chunk].elements.insert(offset, element);
+        self.count += 1;
+        self
+    }
+
+    // like insert, but reports an out of range index instead of ignoring it
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<&mut Self> {
+        if index > self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.insert(index, element))
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> &mut Self {
+        if index < self.count {
+            self.take_at(index);
+        }
+        self
+    }
+
+    // like remove_at, but reports an out of range index instead of ignoring it
+    pub fn try_remove_at(&mut self, index: usize) -> Result<&mut Self> {
+        if index >= self.count {
+            return Err(Error::IndexOutOfBounds { index, len: self.count });
+        }
+
+        Ok(self.remove_at(index))
+    }
+
+    pub fn reverse(&mut self) -> &mut Self {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(index) = cur {
+            cur = self.chunks[index].next;
+            self.chunks[index].next = prev;
+            self.chunks[index].elements.reverse();
+            prev = Some(index);
+        }
+
+        self.tail = self.head;
+        self.head = prev;
+        self
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.chunks.clear();
+        self.free.clear();
+        self.head = None;
+        self.tail = None;
+        self.count = 0;
+        self
+    }
+
+    // moves every element of other onto the end of this list, leaving other empty.
+    // whole chunks are moved over, so this is O(other.size() / CHUNK_CAPACITY) and no element is copied
+    pub fn append_list(&mut self, other: &mut UnrolledList<T>) -> &mut Self {
+        let tail = self.tail;
+        self.link_list_after(tail, other);
+        self
+    }
+
+    // joins the lists in order
+    pub fn concat<I>(lists: I) -> UnrolledList<T>
+        where I: IntoIterator<Item = UnrolledList<T>> {
+        let mut result = UnrolledList::new() as UnrolledList<T>;
+        for mut list in lists {
+            result.append_list(&mut list);
+        }
+        result
+    }
+
+    // splits the list in two at the given index, this list keeps [0, at) and the returned list holds [at, size)
+    // panics when at is past the end of the list, like List::split_off
+    pub fn split_off(&mut self, at: usize) -> UnrolledList<T> {
+        if at > self.count {
+            panic!("{}", Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        // special case at is the size of the list, nothing moves
+        if at == self.count {
+            return UnrolledList::new();
+        }
+
+        let (prev, chunk, offset) = self.locate(at);
+        self.split_located(at, prev, chunk, offset)
+    }
+
+    // like split_off, but reports an out of range index instead of ignoring it
+    pub fn try_split_off(&mut self, at: usize) -> Result<UnrolledList<T>> {
+        if at > self.count {
+            return Err(Error::IndexOutOfBounds { index: at, len: self.count });
+        }
+
+        Ok(self.split_off(at))
+    }
+
+    // consumes the list, returning the elements [0, at) and [at, size) as two lists. panics like split_off
+    pub fn split_at(mut self, at: usize) -> (UnrolledList<T>, UnrolledList<T>) {
+        let back = self.split_off(at);
+        (self, back)
+    }
+
+    // cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front(&self) -> UnrolledListCursor<'_, T> {
+        UnrolledListCursor {
+            current: self.head.map(|head| (head, 0)),
+            index: 0,
+            list: self
+        }
+    }
+
+    // mutable cursor starting at the head, or at the ghost position when the list is empty
+    pub fn cursor_front_mut(&mut self) -> UnrolledListCursorMut<'_, T> {
+        UnrolledListCursorMut {
+            prev: None,
+            current: self.head.map(|head| (head, 0)),
+            index: 0,
+            list: self
+        }
+    }
+
+    // stable sort, the elements are sorted in one buffer and poured back into full chunks
+    pub fn sort_by<F>(&mut self, compare: F) -> &mut Self
+        where F: FnMut(&T, &T) -> Ordering {
+        // nothing to sort
+        if self.count < 2 {
+            return self;
+        }
+
+        let mut elements = Vec::with_capacity(self.count);
+        let mut cur = self.head;
+        while let Some(index) = cur {
+            cur = self.chunks[index].next;
+            elements.append(&mut self.chunks[index].elements);
+        }
+
+        elements.sort_by(compare);
+        self.clear();
+        self.extend(elements);
+        self
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F) -> &mut Self
+        where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // takes a slot for a new chunk, reusing a vacant one when there is any
+    fn alloc(&mut self, elements: Vec<T>, next: Option<usize>) -> usize {
+        let chunk = Chunk { elements, next };
+        match self.free.pop() {
+            Some(index) => {
+                self.chunks[index] = chunk;
+                index
+            },
+            None => {
+                self.chunks.push(chunk);
+                self.chunks.len() - 1
+            },
+        }
+    }
+
+    // vacates a slot that is no longer linked
+    fn dealloc(&mut self, index: usize) {
+        self.chunks[index] = Chunk { elements: Vec::new(), next: None };
+        self.free.push(index);
+    }
+
+    fn is_full(&self, index: usize) -> bool {
+        self.chunks[index].elements.len() == CHUNK_CAPACITY
+    }
+
+    // links a chunk of elements in after the tail, the caller keeps count up to date
+    fn push_chunk(&mut self, elements: Vec<T>) {
+        let index = self.alloc(elements, None);
+        match self.tail {
+            Some(tail) => self.chunks[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+    }
+
+    // the chunk before the one holding position, that chunk and the offset of position in it.
+    // the position must be in range
+    fn locate(&self, mut position: usize) -> (Option<usize>, usize, usize) {
+        let mut prev = None;
+        let mut index = self.head.unwrap();
+        while position >= self.chunks[index].elements.len() {
+            position -= self.chunks[index].elements.len();
+            prev = Some(index);
+            index = self.chunks[index].next.unwrap();
+        }
+        (prev, index, position)
+    }
+
+    // moves the elements from position at on into a new list, at is offset in chunk and prev is the chunk before.
+    // the chunk holding at is cut in two, every chunk after the cut moves over as a whole
+    fn split_located(&mut self, at: usize, prev: Option<usize>, chunk: usize, offset: usize) -> UnrolledList<T> {
+        let mut back = UnrolledList::new() as UnrolledList<T>;
+        let mut cur = Some(chunk);
+        let last = match offset {
+            0 => prev,
+            _ => {
+                back.push_chunk(self.chunks[chunk].elements.split_off(offset));
+                cur = self.chunks[chunk].next.take();
+                Some(chunk)
+            },
+        };
+        match last {
+            Some(last) => self.chunks[last].next = None,
+            None => self.head = None,
+        }
+        self.tail = last;
+
+        while let Some(index) = cur {
+            cur = self.chunks[index].next;
+            back.push_chunk(mem::take(&mut self.chunks[index].elements));
+            self.dealloc(index);
+        }
+
+        back.count = self.count - at;
+        self.count = at;
+        if self.count == 0 {
+            self.clear();
+        }
+        back
+    }
+
+    // moves the chunks of other in after the chunk prev (in front when prev is none), leaving other empty
+    fn link_list_after(&mut self, prev: Option<usize>, other: &mut UnrolledList<T>) {
+        let after = match prev {
+            Some(prev) => self.chunks[prev].next,
+            None => self.head,
+        };
+
+        let mut last = prev;
+        let mut cur = other.head;
+        while let Some(index) = cur {
+            cur = other.chunks[index].next;
+            let new = self.alloc(mem::take(&mut other.chunks[index].elements), after);
+            match last {
+                Some(last) => self.chunks[last].next = Some(new),
+                None => self.head = Some(new),
+            }
+            last = Some(new);
+        }
+
+        if after.is_none() {
+            self.tail = last;
+        }
+        self.count += other.count;
+        other.clear();
+    }
+
+    // chunk and offset of the element after the one at offset in chunk, none past the tail
+    fn next_position(&self, chunk: usize, offset: usize) -> Option<(usize, usize)> {
+        if offset + 1 < self.chunks[chunk].elements.len() {
+            return Some((chunk, offset + 1));
+        }
+        self.chunks[chunk].next.map(|next| (next, 0))
+    }
+
+    // moves the upper half of a full chunk into a new chunk behind it and tells where offset ended up
+    fn split(&mut self, index: usize, offset: usize) -> (usize, usize) {
+        let half = CHUNK_CAPACITY / 2;
+        let mut upper = Vec::with_capacity(CHUNK_CAPACITY);
+        upper.extend(self.chunks[index].elements.drain(half..));
+        let new = self.alloc(upper, self.chunks[index].next);
+        self.chunks[index].next = Some(new);
+        if self.tail == Some(index) {
+            self.tail = Some(new);
+        }
+
+        if offset <= half {
+            (index, offset)
+        } else {
+            (new, offset - half)
+        }
+    }
+
+    // removes the element at position and keeps the chunks at least half full where it can by merging a chunk
+    // with its successor when both fit into one. the position must be in range
+    fn take_at(&mut self, position: usize) -> T {
+        let (prev, index, offset) = self.locate(position);
+        self.take_located(prev, index, offset)
+    }
+
+    // take_at for an element that is already located, prev is the chunk before index
+    fn take_located(&mut self, prev: Option<usize>, index: usize, offset: usize) -> T {
+        let element = self.chunks[index].elements.remove(offset);
+        self.count -= 1;
+
+        let next = self.chunks[index].next;
+        if self.chunks[index].elements.is_empty() {
+            match prev {
+                Some(prev) => self.chunks[prev].next = next,
+                None => self.head = next,
+            }
+            if next.is_none() {
+                self.tail = prev;
+            }
+            self.dealloc(index);
+        } else if let Some(next) = next {
+            let len = self.chunks[index].elements.len();
+            if len < CHUNK_CAPACITY / 2 && len + self.chunks[next].elements.len() <= CHUNK_CAPACITY {
+                let mut moved = mem::take(&mut self.chunks[next].elements);
+                self.chunks[index].elements.append(&mut moved);
+                self.chunks[index].next = self.chunks[next].next;
+                if self.tail == Some(next) {
+                    self.tail = Some(index);
+                }
+                self.dealloc(next);
+            }
+        }
+
+        // special case removed last element in list, start over with an empty slab but keep its capacity
+        if self.count == 0 {
+            self.clear();
+        }
+
+        element
+    }
+
+    fn unlink_front(&mut self) -> Option<T> {
+        // nothing to unlink
+        self.head?;
+        Some(self.take_at(0))
+    }
+}
+
+impl<T: Ord> UnrolledList<T> {
+    pub fn sort(&mut self) -> &mut Self {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    pub fn sort_unstable(&mut self) -> &mut Self {
+        self.sort()
+    }
+}
+
+impl<T: PartialOrd> UnrolledList<T> {
+    pub fn is_sorted(&self) -> bool {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+}
+
+impl<T: PartialEq> UnrolledList<T> {
+    pub fn count(&self, element: &T) -> usize {
+        self.iter().filter(|x| *x == element).count()
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.iter().position(|x| x == element)
+    }
+
+    pub fn has(&self, element: &T) -> bool {
+        self.iter().any(|x| x == element)
+    }
+
+    // removes the first occurrence of element
+    pub fn remove(&mut self, element: &T) -> &mut Self {
+        let _ = self.try_remove(element);
+        self
+    }
+
+    // like remove, but reports when the element is not in the list
+    pub fn try_remove(&mut self, element: &T) -> Result<&mut Self> {
+        let index = self.index(element).ok_or(Error::NotFound)?;
+        self.take_at(index);
+        Ok(self)
+    }
+}
+
+impl<T> Default for UnrolledList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for UnrolledList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for UnrolledList<T> {
+    fn from(array: [T; N]) -> Self {
+        IntoIterator::into_iter(array).collect()
+    }
+}
+
+impl<T> FromIterator<T> for UnrolledList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new() as UnrolledList<T>;
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for UnrolledList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.append(element);
+        }
+    }
+}
+
+impl<'a, T: Clone> Extend<&'a T> for UnrolledList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T> IntoIterator for UnrolledList<T> {
+    type Item = T;
+    type IntoIter = UnrolledListIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        UnrolledListIntoIterator { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a UnrolledList<T> {
+    type Item = &'a T;
+    type IntoIter = UnrolledListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut UnrolledList<T> {
+    type Item = &'a mut T;
+    type IntoIter = UnrolledListIteratorMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// the copy is compacted, its chunks are full apart from the last one
+impl<T: Clone> Clone for UnrolledList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for UnrolledList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for UnrolledList<T> {}
+
+impl<T: PartialOrd> PartialOrd for UnrolledList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for UnrolledList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for UnrolledList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.count);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for UnrolledList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.count {
+            panic!("index out of bounds: the len is {} but the index is {}", self.count, index);
+        }
+
+        let (_, chunk, offset) = self.locate(index);
+        &self.chunks[chunk].elements[offset]
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UnrolledList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// a cursor points at an element of the list or at the "ghost" position, which sits past the tail and before the head.
+// works like the List cursor, with the position of an element being its chunk and its offset in that chunk
+pub struct UnrolledListCursor<'a, T> {
+    current: Option<(usize, usize)>, // chunk and offset, none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a UnrolledList<T>
+}
+
+impl<'a, T> UnrolledListCursor<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some((chunk, offset)) => {
+                self.current = self.list.next_position(chunk, offset);
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head.map(|head| (head, 0));
+                self.index = 0;
+            },
+        }
+    }
+
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        let list = self.list;
+        self.current.map(|(chunk, offset)| &list.chunks[chunk].elements[offset])
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let list = self.list;
+        let (chunk, offset) = match self.current {
+            Some((chunk, offset)) => list.next_position(chunk, offset)?,
+            None => (list.head?, 0),
+        };
+        Some(&list.chunks[chunk].elements[offset])
+    }
+}
+
+// like UnrolledListCursor but able to edit the list. the cursor also tracks the chunk before its own, so every edit
+// only touches the chunks around the cursor and costs O(CHUNK_CAPACITY) rather than a walk from the head
+pub struct UnrolledListCursorMut<'a, T> {
+    prev: Option<usize>, // chunk before the current one, none in the head chunk or at the ghost position
+    current: Option<(usize, usize)>, // chunk and offset, none when at the ghost position
+    index: usize, // equal to the list size at the ghost position
+    list: &'a mut UnrolledList<T>
+}
+
+impl<'a, T> UnrolledListCursorMut<'a, T> {
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some((chunk, offset)) => {
+                self.current = self.list.next_position(chunk, offset);
+                match self.current {
+                    Some((next, _)) if next != chunk => self.prev = Some(chunk),
+                    Some(_) => {},
+                    // at the ghost position there is no chunk before the cursor
+                    None => self.prev = None,
+                }
+                self.index += 1;
+            },
+            None => {
+                self.current = self.list.head.map(|head| (head, 0));
+                self.prev = None;
+                self.index = 0;
+            },
+        }
+    }
+
+    // position of the cursor, none at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        let (chunk, offset) = self.current?;
+        Some(&mut self.list.chunks[chunk].elements[offset])
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let (chunk, offset) = match self.current {
+            Some((chunk, offset)) => self.list.next_position(chunk, offset)?,
+            None => (self.list.head?, 0),
+        };
+        Some(&mut self.list.chunks[chunk].elements[offset])
+    }
+
+    // read only view of the cursor at the same position
+    pub fn as_cursor(&self) -> UnrolledListCursor<'_, T> {
+        UnrolledListCursor {
+            current: self.current,
+            index: self.index,
+            list: self.list
+        }
+    }
+
+    // inserts after the current element, at the ghost position the element becomes the new head
+    pub fn insert_after(&mut self, element: T) {
+        match self.current {
+            Some((_, offset)) => self.insert_in(offset + 1, element),
+            None => {
+                self.list.prepend(element);
+                self.index = self.list.count;
+            },
+        }
+    }
+
+    // inserts before the current element, at the ghost position the element becomes the new tail
+    pub fn insert_before(&mut self, element: T) {
+        match self.current {
+            Some((_, offset)) => {
+                self.insert_in(offset, element);
+                self.index += 1;
+            },
+            None => {
+                self.list.append(element);
+                self.index = self.list.count;
+            },
+        }
+    }
+
+    // removes the current element and moves the cursor to the element after it
+    pub fn remove_current(&mut self) -> Option<T> {
+        let (chunk, offset) = self.current?;
+        let emptied = self.list.chunks[chunk].elements.len() == 1;
+        let next = self.list.chunks[chunk].next;
+        let element = self.list.take_located(self.prev, chunk, offset);
+
+        // the chunk is gone when it held only this element, otherwise the elements after it may have been merged in
+        self.current = if self.list.count == 0 {
+            None
+        } else if emptied {
+            next.map(|next| (next, 0))
+        } else if offset < self.list.chunks[chunk].elements.len() {
+            Some((chunk, offset))
+        } else {
+            let next = self.list.chunks[chunk].next;
+            self.prev = next.map(|_| chunk);
+            next.map(|next| (next, 0))
+        };
+
+        if self.current.is_none() {
+            // the tail was removed, the cursor ends up at the ghost position
+            self.prev = None;
+        }
+        Some(element)
+    }
+
+    // moves every element after the current one into a new list, at the ghost position the whole list is moved
+    pub fn split_after(&mut self) -> UnrolledList<T> {
+        match self.current {
+            Some((chunk, offset)) => {
+                let (prev, at_chunk, at_offset) = match self.list.next_position(chunk, offset) {
+                    Some((next, next_offset)) if next == chunk => (self.prev, chunk, next_offset),
+                    Some((next, _)) => (Some(chunk), next, 0),
+                    None => return UnrolledList::new(),
+                };
+                self.list.split_located(self.index + 1, prev, at_chunk, at_offset)
+            },
+            None => {
+                self.index = 0;
+                mem::take(self.list)
+            },
+        }
+    }
+
+    // moves every element of other in after the current element, at the ghost position they become the new front.
+    // the chunks of other are linked in as they are, only the current chunk is cut in two
+    pub fn splice_after(&mut self, mut other: UnrolledList<T>) {
+        if other.is_empty() {
+            return;
+        }
+
+        match self.current {
+            Some((chunk, offset)) => {
+                let rest = self.list.chunks[chunk].elements.split_off(offset + 1);
+                if !rest.is_empty() {
+                    let new = self.list.alloc(rest, self.list.chunks[chunk].next);
+                    self.list.chunks[chunk].next = Some(new);
+                    if self.list.tail == Some(chunk) {
+                        self.list.tail = Some(new);
+                    }
+                }
+                self.list.link_list_after(Some(chunk), &mut other);
+            },
+            None => {
+                self.list.link_list_after(None, &mut other);
+                self.index = self.list.count;
+            },
+        }
+    }
+
+    // inserts element at offset in the chunk of the cursor, splitting the chunk first when it is full. the cursor
+    // keeps pointing at the same element
+    fn insert_in(&mut self, offset: usize, element: T) {
+        let (chunk, current_offset) = self.current.unwrap();
+        let (at_chunk, at_offset) = if self.list.is_full(chunk) {
+            let half = CHUNK_CAPACITY / 2;
+            let at = self.list.split(chunk, offset);
+            if current_offset >= half {
+                self.prev = Some(chunk);
+                self.current = Some((self.list.chunks[chunk].next.unwrap(), current_offset - half));
+            }
+            at
+        } else {
+            (chunk, offset)
+        };
+
+        self.list.chunks[at_chunk].elements.insert(at_offset, element);
+        self.list.count += 1;
+        if let Some((current_chunk, current_offset)) = &mut self.current {
+            if *current_chunk == at_chunk && *current_offset >= at_offset {
+                *current_offset += 1;
+            }
+        }
+    }
+}
+
+pub struct UnrolledListIterator<'a, T> {
+    list: &'a UnrolledList<T>,
+    current: Option<usize>, // the next chunk to walk
+    elements: slice::Iter<'a, T>, // what is left of the chunk being walked
+    len: usize // elements left to yield
+}
+
+pub struct UnrolledListIteratorMut<'a, T> {
+    chunks: *mut Chunk<T>, // start of the slab, the iterator holds the only borrow of it
+    current: Option<usize>, // the next chunk to walk
+    elements: slice::IterMut<'a, T>, // what is left of the chunk being walked
+    len: usize, // elements left to yield
+    marker: PhantomData<&'a mut T> // the iterator mutably borrows the list it was created from
+}
+
+pub struct UnrolledListIntoIterator<T> {
+    list: UnrolledList<T>
+}
+
+impl<'a, T> Iterator for UnrolledListIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                self.len -= 1;
+                return Some(element);
+            }
+
+            let chunk = &self.list.chunks[self.current?];
+            self.current = chunk.next;
+            self.elements = chunk.elements.iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for UnrolledListIterator<'a, T> {}
+
+impl<'a, T> Iterator for UnrolledListIteratorMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                self.len -= 1;
+                return Some(element);
+            }
+
+            // SAFETY: the slab is borrowed mutably for 'a and cannot grow or shrink while the iterator lives, so
+            // the chunk stays valid. the list holds no cycles, so every chunk is visited at most once and the
+            // returned references never alias.
+            let chunk = unsafe { &mut *self.chunks.add(self.current?) };
+            self.current = chunk.next;
+            self.elements = chunk.elements.iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for UnrolledListIteratorMut<'a, T> {}
+
+impl<T> Iterator for UnrolledListIntoIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.unlink_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.count, Some(self.list.count))
+    }
+}
+
+impl<T> ExactSizeIterator for UnrolledListIntoIterator<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{UnrolledList, CHUNK_CAPACITY};
+    use crate::error::Error;
+
+    // every linked chunk holds between 1 and CHUNK_CAPACITY elements and the tail is the last chunk
+    fn check_chunks<T>(list: &UnrolledList<T>) {
+        let mut total = 0;
+        let mut last = None;
+        let mut cur = list.head;
+        while let Some(index) = cur {
+            let len = list.chunks[index].elements.len();
+            assert!(len > 0 && len <= CHUNK_CAPACITY);
+            total += len;
+            last = cur;
+            cur = list.chunks[index].next;
+        }
+        assert_eq!(total, list.count);
+        assert_eq!(last, list.tail);
+    }
+
+    #[test]
+    fn ulist_new() {
+        let list = UnrolledList::new() as UnrolledList<i32>;
+        assert!(list.is_empty());
+        assert_eq!(list.iter().next(), None);
+        check_chunks(&list);
+    }
+
+    #[test]
+    fn ulist_prepend_append_fill_chunks() {
+        let mut list = UnrolledList::new();
+        for x in 0..100 {
+            list.append(x);
+        }
+        for x in (-100..0).rev() {
+            list.prepend(x);
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), (-100..100).collect::<Vec<i32>>());
+        assert!(list.chunks.len() <= 200 / CHUNK_CAPACITY + 2);
+        check_chunks(&list);
+    }
+
+    #[test]
+    fn ulist_insert_splits() {
+        let mut list: UnrolledList<i32> = (0..CHUNK_CAPACITY as i32).collect();
+        list.insert(3, 100).insert(14, 200).insert(100, 300);
+        let mut expected: Vec<i32> = (0..CHUNK_CAPACITY as i32).collect();
+        expected.insert(3, 100);
+        expected.insert(14, 200);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), expected);
+        assert_eq!(list.try_insert(100, 0).err(), Some(Error::IndexOutOfBounds { index: 100, len: 18 }));
+        check_chunks(&list);
+    }
+
+    #[test]
+    fn ulist_remove_merges() {
+        let mut list: UnrolledList<i32> = (0..64).collect();
+        for _ in 0..40 {
+            list.remove_at(10);
+        }
+        assert_eq!(list.size(), 24);
+        assert_eq!(list[9], 9);
+        assert_eq!(list[10], 50);
+        check_chunks(&list);
+        assert!(list.free.len() >= 2);
+
+        list.remove(&50).remove(&1000);
+        assert_eq!(list.try_remove(&1000).err(), Some(Error::NotFound));
+        assert_eq!(list.try_remove_at(23).err(), Some(Error::IndexOutOfBounds { index: 23, len: 23 }));
+        check_chunks(&list);
+    }
+
+    #[test]
+    fn ulist_matches_vec() {
+        let mut list = UnrolledList::new();
+        let mut model = Vec::new();
+        let mut seed = 12345u64;
+        for step in 0..3000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let at = (seed >> 33) as usize % (model.len() + 1);
+            if seed % 5 < 3 || model.is_empty() {
+                list.insert(at, step);
+                model.insert(at, step);
+            } else {
+                let at = at.min(model.len() - 1);
+                list.remove_at(at);
+                model.remove(at);
+            }
+        }
+        check_chunks(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), model);
+        list.for_each_mut(|x| *x *= 2);
+        assert!(list.iter().zip(model.iter()).all(|(a, b)| *a == b * 2));
+    }
+
+    #[test]
+    fn ulist_reverse_sort() {
+        let mut list: UnrolledList<i32> = (0..50).collect();
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), (0..50).rev().collect::<Vec<i32>>());
+        check_chunks(&list);
+        list.sort();
+        assert!(list.is_sorted());
+        list.sort_by_key(|x| x % 10);
+        assert_eq!(list.iter().take(5).copied().collect::<Vec<i32>>(), vec![0, 10, 20, 30, 40]);
+        check_chunks(&list);
+    }
+
+    #[test]
+    fn ulist_split_and_append() {
+        let mut list: UnrolledList<i32> = (0..40).collect();
+        let back = list.split_off(21);
+        check_chunks(&list);
+        check_chunks(&back);
+        assert_eq!(list, (0..21).collect());
+        assert_eq!(back, (21..40).collect());
+
+        let (a, b) = back.split_at(16);
+        assert_eq!(a.size(), 16);
+        assert_eq!(b.size(), 3);
+        let joined = UnrolledList::concat(vec![list, a, b]);
+        check_chunks(&joined);
+        assert_eq!(joined, (0..40).collect());
+
+        let mut joined = joined;
+        assert!(joined.try_split_off(41).is_err());
+        assert!(joined.split_off(40).is_empty());
+        assert_eq!(joined.split_off(0).size(), 40);
+        assert!(joined.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 3")]
+    fn ulist_split_off_out_of_bounds() {
+        let mut list = UnrolledList::from([0, 1]);
+        list.split_off(3);
+    }
+
+    #[test]
+    fn ulist_cursor() {
+        let list: UnrolledList<usize> = (0..CHUNK_CAPACITY + 2).collect();
+        let mut cursor = list.cursor_front();
+        assert_eq!((cursor.current(), cursor.peek_next()), (Some(&0), Some(&1)));
+        for _ in 0..CHUNK_CAPACITY {
+            cursor.move_next();
+        }
+        // crossed into the second chunk
+        assert_eq!((cursor.current(), cursor.index()), (Some(&CHUNK_CAPACITY), Some(CHUNK_CAPACITY)));
+        cursor.move_next();
+        assert_eq!(cursor.peek_next(), None);
+
+        // the ghost position sits between the tail and the head
+        cursor.move_next();
+        assert_eq!((cursor.current(), cursor.index()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&0));
+        assert_eq!((UnrolledList::new() as UnrolledList<i32>).cursor_front().current(), None);
+    }
+
+    #[test]
+    fn ulist_cursor_mut_matches_vec() {
+        // random walks over the list, editing at the cursor and checking it against a vec and an index
+        let mut list = UnrolledList::new();
+        let mut model = Vec::new();
+        let mut seed = 777u64;
+        let mut cursor = list.cursor_front_mut();
+        let mut at = None;
+        for step in 0..4000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            match (seed >> 33) % 6 {
+                0 | 1 => {
+                    cursor.move_next();
+                    at = match at {
+                        Some(i) if i + 1 < model.len() => Some(i + 1),
+                        Some(_) => None,
+                        None if model.is_empty() => None,
+                        None => Some(0),
+                    };
+                },
+                2 => {
+                    cursor.insert_after(step);
+                    match at {
+                        Some(i) => model.insert(i + 1, step),
+                        None => model.insert(0, step),
+                    }
+                },
+                3 => {
+                    cursor.insert_before(step);
+                    match at {
+                        Some(i) => {
+                            model.insert(i, step);
+                            at = Some(i + 1);
+                        },
+                        None => model.push(step),
+                    }
+                },
+                _ => {
+                    let removed = at.map(|i| model.remove(i));
+                    assert_eq!(cursor.remove_current(), removed);
+                    if at == Some(model.len()) {
+                        at = None;
+                    }
+                },
+            }
+            assert_eq!(cursor.index(), at);
+            assert_eq!(cursor.current().copied(), at.map(|i| model[i]));
+        }
+        check_chunks(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<i32>>(), model);
+    }
+
+    #[test]
+    fn ulist_cursor_mut_split_splice() {
+        let mut list: UnrolledList<i32> = (0..40).collect();
+        let mut cursor = list.cursor_front_mut();
+        for _ in 0..20 {
+            cursor.move_next();
+        }
+        let back = cursor.split_after();
+        cursor.splice_after((100..120).collect());
+        cursor.splice_after(UnrolledList::new());
+        assert_eq!(cursor.peek_next(), Some(&mut 100));
+        check_chunks(&list);
+        check_chunks(&back);
+        assert_eq!(back, (21..40).collect());
+        assert_eq!(list, (0..21).chain(100..120).collect());
+
+        // cut inside a chunk and at a chunk boundary
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_after(UnrolledList::from([-1, -2]));
+        for _ in 0..CHUNK_CAPACITY {
+            cursor.move_next();
+        }
+        let tail = cursor.split_after();
+        check_chunks(&list);
+        check_chunks(&tail);
+        assert_eq!(list.size() + tail.size(), 43);
+        list.append(1000);
+        assert_eq!(list.iter().last(), Some(&1000));
+
+        // at the ghost position the whole list moves out and spliced lists go to the front
+        let mut list = UnrolledList::from([3]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.split_after(), UnrolledList::from([3]));
+        cursor.splice_after(UnrolledList::from([2]));
+        cursor.splice_after(UnrolledList::from([0, 1]));
+        check_chunks(&list);
+        list.append(3);
+        assert_eq!(list, UnrolledList::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn ulist_search_and_traits() {
+        let list = UnrolledList::from([1, 2, 3, 2]);
+        assert_eq!(list.count(&2), 2);
+        assert_eq!(list.index(&3), Some(2));
+        assert!(list.has(&1));
+        assert!(!list.has(&100));
+        assert_eq!(format!("{:?}", list.clone()), "[1, 2, 3, 2]");
+        assert!(list < UnrolledList::from([1, 3]));
+        assert_eq!(list.into_iter().collect::<Vec<i32>>(), vec![1, 2, 3, 2]);
+    }
+}
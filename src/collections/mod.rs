@@ -1 +1,13 @@
-pub mod lists;
\ No newline at end of file
+pub mod deques;
+pub mod heaps;
+pub mod lists;
+pub mod skiplists;
+pub mod stacks_queues;
+
+pub use self::deques::{Overflow, RingDeque};
+pub use self::heaps::{BinaryHeap, BinomialHandle, BinomialHeap, FibonacciHandle, FibonacciHeap, IndexedPriorityQueue,
+                      MergeableHeap, PairingHandle, PairingHeap, QueueHandle};
+pub use self::lists::{ArenaList, CircularList, DoublyLinkedList, List, LockFreeList, PersistentList, SyncList,
+                      UnrolledList};
+pub use self::skiplists::{SkipListMap, SkipListSet, SyncSkipListMap};
+pub use self::stacks_queues::{Queue, Stack};
@@ -0,0 +1,7 @@
+pub mod skiplistmap;
+pub mod skiplistset;
+pub mod syncskiplistmap;
+
+pub use self::skiplistmap::{SkipListMap, SkipListMapIterator};
+pub use self::skiplistset::{SkipListSet, SkipListSetIterator};
+pub use self::syncskiplistmap::SyncSkipListMap;
@@ -0,0 +1,493 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+
+const MAX_LEVEL: usize = 32; // plenty for 2^32 entries at one level in two
+const HEAD: usize = 0; // slot of the head node
+
+#[derive(Clone, Copy)]
+struct Link {
+    next: Option<usize>, // slot of the next node on this level, none at the end
+    span: usize // how many positions the link moves forward, a missing next spans up to the last entry
+}
+
+struct SkipNode<K, V> {
+    entry: Option<(K, V)>, // none for the head and for vacant slots
+    links: Vec<Link> // one link per level the node is on, level 0 links every node
+}
+
+// xorshift64*, good enough to pick levels and seedable so tests can rebuild the exact same list
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        // the all zero state never leaves zero
+        XorShift(if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed })
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // every level has half the nodes of the one below it
+    fn level(&mut self) -> usize {
+        (self.next().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+// ordered map on a skip list. the nodes live in one slab and link to each other by index like ArenaList, every
+// link also remembers how many entries it skips, which makes rank and select as cheap as a lookup, O(log n).
+// SyncSkipListMap shares one between threads.
+pub struct SkipListMap<K, V> {
+    nodes: Vec<SkipNode<K, V>>, // the slab, nodes[HEAD] is the head and is on every level in use
+    free: Vec<usize>, // vacant slots, reused before the slab grows
+    count: usize, // the amount of entries in the map
+    rng: XorShift
+}
+
+impl<K, V> SkipListMap<K, V> {
+    pub fn new() -> Self {
+        SkipListMap::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    // the levels are drawn from a generator seeded with seed, the same inserts give the same list every time
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            nodes: vec![SkipNode { entry: None, links: Vec::new() }],
+            free: Vec::new(),
+            count: 0,
+            rng: XorShift::new(seed)
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn level(&self) -> usize {
+        self.nodes[HEAD].links.len()
+    }
+
+    fn entry(&self, index: usize) -> (&K, &V) {
+        let (key, value) = self.nodes[index].entry.as_ref().expect("linked slot is vacant");
+        (key, value)
+    }
+
+    fn key(&self, index: usize) -> &K {
+        self.entry(index).0
+    }
+
+    fn first_node(&self) -> Option<usize> {
+        self.nodes[HEAD].links.first().and_then(|link| link.next)
+    }
+
+    // the entry at position index in key order, O(log n)
+    pub fn select(&self, index: usize) -> Option<(&K, &V)> {
+        let target = index + 1; // the head is position 0
+        let mut x = HEAD;
+        let mut passed = 0;
+        for lvl in (0..self.level()).rev() {
+            while let Some(next) = self.nodes[x].links[lvl].next {
+                if passed + self.nodes[x].links[lvl].span > target {
+                    break;
+                }
+                passed += self.nodes[x].links[lvl].span;
+                x = next;
+            }
+            if passed == target {
+                return Some(self.entry(x));
+            }
+        }
+        None
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.first_node().map(|index| self.entry(index))
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.count.checked_sub(1).and_then(|index| self.select(index))
+    }
+
+    pub fn iter(&self) -> SkipListMapIterator<'_, K, V> {
+        SkipListMapIterator {
+            map: self,
+            next: self.first_node(),
+            len: self.count
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.nodes.truncate(1);
+        self.nodes[HEAD].links.clear();
+        self.free.clear();
+        self.count = 0;
+        self
+    }
+
+    fn alloc(&mut self, node: SkipNode<K, V>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            },
+        }
+    }
+}
+
+impl<K: Ord, V> SkipListMap<K, V> {
+    // finds the last node on every level that comes before key, and the positions of those nodes
+    fn predecessors(&self, key: &K) -> ([usize; MAX_LEVEL], [usize; MAX_LEVEL]) {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut x = HEAD;
+        let mut passed = 0;
+        for lvl in (0..self.level()).rev() {
+            while let Some(next) = self.nodes[x].links[lvl].next {
+                if self.key(next) >= key {
+                    break;
+                }
+                passed += self.nodes[x].links[lvl].span;
+                x = next;
+            }
+            update[lvl] = x;
+            rank[lvl] = passed;
+        }
+        (update, rank)
+    }
+
+    // the first node not before key (or after key when past_equal is set) and its position in key order
+    fn seek(&self, key: &K, past_equal: bool) -> (Option<usize>, usize) {
+        let mut x = HEAD;
+        let mut passed = 0;
+        for lvl in (0..self.level()).rev() {
+            while let Some(next) = self.nodes[x].links[lvl].next {
+                let next_key = self.key(next);
+                if next_key > key || (next_key == key && !past_equal) {
+                    break;
+                }
+                passed += self.nodes[x].links[lvl].span;
+                x = next;
+            }
+        }
+        (self.nodes[x].links.first().and_then(|link| link.next), passed)
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        match self.seek(key, false) {
+            (Some(index), _) if self.key(index) == key => Some(index),
+            _ => None,
+        }
+    }
+
+    // adds the entry, when key is already in the map only its value is replaced and the old one handed back
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (mut update, mut rank) = self.predecessors(&key);
+        if let Some(index) = self.nodes[update[0]].links.first().and_then(|link| link.next) {
+            if self.key(index) == &key {
+                let (_, old) = self.nodes[index].entry.as_mut().unwrap();
+                return Some(mem::replace(old, value));
+            }
+        }
+
+        // new levels start out at the head with a link that spans the whole list
+        let height = self.rng.level();
+        for lvl in self.level()..height {
+            update[lvl] = HEAD;
+            rank[lvl] = 0;
+            self.nodes[HEAD].links.push(Link { next: None, span: self.count });
+        }
+
+        let index = self.alloc(SkipNode { entry: Some((key, value)), links: Vec::with_capacity(height) });
+        for lvl in 0..height {
+            // the new node takes over the rest of the link from its predecessor
+            let passed = rank[0] - rank[lvl];
+            let prev = &mut self.nodes[update[lvl]].links[lvl];
+            let link = Link { next: prev.next, span: prev.span - passed };
+            prev.next = Some(index);
+            prev.span = passed + 1;
+            self.nodes[index].links.push(link);
+        }
+
+        // links above the new node now jump over one more entry
+        let level = self.level();
+        for (lvl, &prev) in update.iter().enumerate().take(level).skip(height) {
+            self.nodes[prev].links[lvl].span += 1;
+        }
+
+        self.count += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (update, _) = self.predecessors(key);
+        let index = self.nodes[update[0]].links.first().and_then(|link| link.next)?;
+        if self.key(index) != key {
+            return None;
+        }
+
+        let level = self.level();
+        for (lvl, &prev) in update.iter().enumerate().take(level) {
+            if self.nodes[prev].links[lvl].next == Some(index) {
+                // the link of the last node to the end spans nothing, so add before taking the node away
+                let Link { next, span } = self.nodes[index].links[lvl];
+                let prev = &mut self.nodes[prev].links[lvl];
+                prev.next = next;
+                prev.span = prev.span + span - 1;
+            } else {
+                self.nodes[prev].links[lvl].span -= 1;
+            }
+        }
+
+        // drop the levels nothing is on anymore
+        while self.nodes[HEAD].links.last().is_some_and(|link| link.next.is_none()) {
+            self.nodes[HEAD].links.pop();
+        }
+
+        let node = mem::replace(&mut self.nodes[index], SkipNode { entry: None, links: Vec::new() });
+        self.free.push(index);
+        self.count -= 1;
+        node.entry.map(|(_, value)| value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|index| self.entry(index).1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        self.nodes[index].entry.as_mut().map(|(_, value)| value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    // the position of key in key order, none when it is not in the map, O(log n)
+    pub fn rank(&self, key: &K) -> Option<usize> {
+        match self.seek(key, false) {
+            (Some(index), rank) if self.key(index) == key => Some(rank),
+            _ => None,
+        }
+    }
+
+    // the entries with keys inside range, in key order
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> SkipListMapIterator<'_, K, V> {
+        let (next, start) = match range.start_bound() {
+            Bound::Included(key) => self.seek(key, false),
+            Bound::Excluded(key) => self.seek(key, true),
+            Bound::Unbounded => (self.first_node(), 0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.seek(key, true).1,
+            Bound::Excluded(key) => self.seek(key, false).1,
+            Bound::Unbounded => self.count,
+        };
+
+        SkipListMapIterator {
+            map: self,
+            next,
+            len: end.saturating_sub(start)
+        }
+    }
+}
+
+impl<K, V> Default for SkipListMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipListMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipListMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipListMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipListMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = SkipListMapIterator<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SkipListMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for SkipListMap<K, V> {}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SkipListMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub struct SkipListMapIterator<'a, K, V> {
+    map: &'a SkipListMap<K, V>,
+    next: Option<usize>,
+    len: usize // entries left to yield, a range stops before the end of the list
+}
+
+impl<'a, K, V> Iterator for SkipListMapIterator<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let index = self.next?;
+        self.next = self.map.nodes[index].links[0].next;
+        self.len -= 1;
+        Some(self.map.entry(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for SkipListMapIterator<'a, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{SkipListMap, XorShift};
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    #[test]
+    fn slmap_new() {
+        let map = SkipListMap::new() as SkipListMap<i32, i32>;
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.first(), None);
+        assert_eq!(map.last(), None);
+        assert_eq!(map.select(0), None);
+        assert_eq!(map.range(..).count(), 0);
+    }
+
+    #[test]
+    fn slmap_insert_get() {
+        let mut map = SkipListMap::with_seed(1);
+        assert_eq!(map.insert(3, "c"), None);
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(2, "B"), Some("b"));
+        assert_eq!(map.size(), 3);
+        assert_eq!(map.get(&2), Some(&"B"));
+        assert_eq!(map.get(&4), None);
+        *map.get_mut(&1).unwrap() = "A";
+        assert_eq!(format!("{:?}", map), r#"{1: "A", 2: "B", 3: "c"}"#);
+    }
+
+    #[test]
+    fn slmap_remove() {
+        let mut map: SkipListMap<i32, i32> = (0..10).map(|x| (x, x * x)).collect();
+        assert_eq!(map.remove(&3), Some(9));
+        assert_eq!(map.remove(&3), None);
+        assert!(!map.contains_key(&3));
+        assert_eq!(map.keys().copied().collect::<Vec<i32>>(), vec![0, 1, 2, 4, 5, 6, 7, 8, 9]);
+        for x in 0..10 {
+            map.remove(&x);
+        }
+        assert!(map.is_empty());
+        map.insert(1, 1);
+        assert_eq!(map.first(), Some((&1, &1)));
+    }
+
+    #[test]
+    fn slmap_first_last_rank_select() {
+        let map: SkipListMap<i32, ()> = (0..100).map(|x| (x * 2, ())).collect();
+        assert_eq!(map.first(), Some((&0, &())));
+        assert_eq!(map.last(), Some((&198, &())));
+        assert_eq!(map.rank(&50), Some(25));
+        assert_eq!(map.rank(&51), None);
+        assert_eq!(map.select(25), Some((&50, &())));
+        assert_eq!(map.select(100), None);
+    }
+
+    #[test]
+    fn slmap_range() {
+        let map: SkipListMap<i32, i32> = (0..20).map(|x| (x, -x)).collect();
+        assert_eq!(map.range(5..8).map(|(k, _)| *k).collect::<Vec<i32>>(), vec![5, 6, 7]);
+        assert_eq!(map.range(5..=8).len(), 4);
+        assert_eq!(map.range(..3).map(|(_, v)| *v).collect::<Vec<i32>>(), vec![0, -1, -2]);
+        assert_eq!(map.range(17..).count(), 3);
+        assert_eq!(map.range(30..40).count(), 0);
+        assert_eq!(map.range((Bound::Included(8), Bound::Excluded(5))).count(), 0);
+    }
+
+    #[test]
+    fn slmap_same_seed_same_levels() {
+        let build = || {
+            let mut map = SkipListMap::with_seed(42);
+            map.extend((0..500).map(|x| (x, ())));
+            map
+        };
+        let (a, b) = (build(), build());
+        let heights = |m: &SkipListMap<i32, ()>| m.nodes.iter().map(|n| n.links.len()).collect::<Vec<usize>>();
+        assert_eq!(heights(&a), heights(&b));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn slmap_matches_btreemap() {
+        let mut rng = XorShift::new(7);
+        let mut map = SkipListMap::with_seed(7);
+        let mut model = BTreeMap::new();
+        for _ in 0..5000 {
+            let key = rng.next() % 300;
+            if rng.next().is_multiple_of(3) {
+                assert_eq!(map.remove(&key), model.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, key + 1), model.insert(key, key + 1));
+            }
+        }
+
+        assert_eq!(map.size(), model.len());
+        assert!(map.iter().eq(model.iter()));
+        for (rank, key) in model.keys().enumerate() {
+            assert_eq!(map.rank(key), Some(rank));
+            assert_eq!(map.select(rank).map(|(k, _)| k), Some(key));
+        }
+        assert!(map.range(50..120).eq(model.range(50..120)));
+        assert_eq!(map.last(), model.iter().next_back());
+    }
+}
@@ -0,0 +1,185 @@
+use super::skiplistmap::{SkipListMap, SkipListMapIterator};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+
+// ordered set on a skip list, a SkipListMap without values
+pub struct SkipListSet<T> {
+    map: SkipListMap<T, ()>
+}
+
+impl<T> SkipListSet<T> {
+    pub fn new() -> Self {
+        Self {
+            map: SkipListMap::new()
+        }
+    }
+
+    // the levels are drawn from a generator seeded with seed, the same inserts give the same list every time
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            map: SkipListMap::with_seed(seed)
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.map.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // the element at position index in order, O(log n)
+    pub fn select(&self, index: usize) -> Option<&T> {
+        self.map.select(index).map(|(element, _)| element)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(element, _)| element)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(element, _)| element)
+    }
+
+    pub fn iter(&self) -> SkipListSetIterator<'_, T> {
+        SkipListSetIterator {
+            iter: self.map.iter()
+        }
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.map.clear();
+        self
+    }
+}
+
+impl<T: Ord> SkipListSet<T> {
+    // false when element was already in the set, the set is left as it was then
+    pub fn insert(&mut self, element: T) -> bool {
+        if self.map.contains_key(&element) {
+            return false;
+        }
+        self.map.insert(element, ());
+        true
+    }
+
+    pub fn remove(&mut self, element: &T) -> bool {
+        self.map.remove(element).is_some()
+    }
+
+    pub fn contains(&self, element: &T) -> bool {
+        self.map.contains_key(element)
+    }
+
+    // the position of element in order, none when it is not in the set, O(log n)
+    pub fn rank(&self, element: &T) -> Option<usize> {
+        self.map.rank(element)
+    }
+
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> SkipListSetIterator<'_, T> {
+        SkipListSetIterator {
+            iter: self.map.range(range)
+        }
+    }
+}
+
+impl<T> Default for SkipListSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for SkipListSet<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SkipListSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = SkipListSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Ord> Extend<T> for SkipListSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipListSet<T> {
+    type Item = &'a T;
+    type IntoIter = SkipListSetIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SkipListSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T: Eq> Eq for SkipListSet<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for SkipListSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+pub struct SkipListSetIterator<'a, T> {
+    iter: SkipListMapIterator<'a, T, ()>
+}
+
+impl<'a, T> Iterator for SkipListSetIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(element, _)| element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SkipListSetIterator<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::SkipListSet;
+
+    #[test]
+    fn slset_insert_remove() {
+        let mut set = SkipListSet::with_seed(3);
+        assert!(set.insert(5));
+        assert!(set.insert(1));
+        assert!(!set.insert(5));
+        assert!(set.contains(&1));
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(set.size(), 1);
+        assert_eq!(format!("{:?}", set), "{5}");
+    }
+
+    #[test]
+    fn slset_order_and_queries() {
+        let set = SkipListSet::from(vec![9, 3, 7, 1, 5, 3]);
+        assert_eq!(set.iter().copied().collect::<Vec<i32>>(), vec![1, 3, 5, 7, 9]);
+        assert_eq!(set.first(), Some(&1));
+        assert_eq!(set.last(), Some(&9));
+        assert_eq!(set.rank(&7), Some(3));
+        assert_eq!(set.select(1), Some(&3));
+        assert_eq!(set.range(2..8).copied().collect::<Vec<i32>>(), vec![3, 5, 7]);
+        assert_eq!(set, vec![1, 3, 5, 7, 9].into_iter().collect());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::collections::skiplists::SkipListMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+// SkipListMap that can be shared between threads, a coarse RwLock around the whole map. inserts and removes take
+// &self but hold the write lock for the entire operation, so writers are serialized and also wait for every reader.
+// lookups share the read lock and run in parallel. insert_all takes the lock once for a whole batch, which keeps
+// the locking overhead down when many threads feed one map
+pub struct SyncSkipListMap<K, V> {
+    map: RwLock<SkipListMap<K, V>>
+}
+
+impl<K, V> SyncSkipListMap<K, V> {
+    pub fn new() -> Self {
+        SyncSkipListMap::from(SkipListMap::new())
+    }
+
+    // see SkipListMap::with_seed
+    pub fn with_seed(seed: u64) -> Self {
+        SyncSkipListMap::from(SkipListMap::with_seed(seed))
+    }
+
+    // every update is finished before the lock is released, so a panic while it was held never leaves the map
+    // half updated and poisoning can be ignored
+    pub fn read(&self) -> RwLockReadGuard<'_, SkipListMap<K, V>> {
+        self.map.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // exclusive access for anything the shared methods do not cover, other threads wait until the guard is dropped
+    pub fn write(&self) -> RwLockWriteGuard<'_, SkipListMap<K, V>> {
+        self.map.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn size(&self) -> usize {
+        self.read().size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    pub fn clear(&self) -> &Self {
+        self.write().clear();
+        self
+    }
+
+    pub fn into_inner(self) -> SkipListMap<K, V> {
+        self.map.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<K: Ord, V> SyncSkipListMap<K, V> {
+    // waits for the write lock, one insert or remove runs at a time
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.write().insert(key, value)
+    }
+
+    // inserts every entry under a single write lock
+    pub fn insert_all<I: IntoIterator<Item = (K, V)>>(&self, iter: I) -> &Self {
+        self.write().extend(iter);
+        self
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.write().remove(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.read().contains_key(key)
+    }
+
+    pub fn rank(&self, key: &K) -> Option<usize> {
+        self.read().rank(key)
+    }
+}
+
+// values are cloned out, the map may change as soon as the lock is released. use read for borrowed access
+impl<K: Ord + Clone, V: Clone> SyncSkipListMap<K, V> {
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.read().get(key).cloned()
+    }
+
+    pub fn first(&self) -> Option<(K, V)> {
+        self.read().first().map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    pub fn last(&self) -> Option<(K, V)> {
+        self.read().last().map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    // snapshot of the entries in key order
+    pub fn to_vec(&self) -> Vec<(K, V)> {
+        self.read().iter().map(|(key, value)| (key.clone(), value.clone())).collect()
+    }
+}
+
+impl<K, V> Default for SyncSkipListMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> From<SkipListMap<K, V>> for SyncSkipListMap<K, V> {
+    fn from(map: SkipListMap<K, V>) -> Self {
+        Self {
+            map: RwLock::new(map)
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SyncSkipListMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        SyncSkipListMap::from(iter.into_iter().collect::<SkipListMap<K, V>>())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SyncSkipListMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.read().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncSkipListMap;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn sskip_insert_get_remove() {
+        let map = SyncSkipListMap::with_seed(3);
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(2, "B"), Some("b"));
+        assert_eq!(map.get(&2), Some("B"));
+        assert_eq!(map.rank(&2), Some(1));
+        assert_eq!(map.first(), Some((1, "a")));
+        assert_eq!(map.remove(&1), Some("a"));
+        assert_eq!(map.size(), 1);
+        assert_eq!(map.read().range(..).count(), 1);
+        assert_eq!(format!("{:?}", map), r#"{2: "B"}"#);
+    }
+
+    #[test]
+    fn sskip_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SyncSkipListMap<i32, String>>();
+    }
+
+    #[test]
+    fn sskip_concurrent_inserters_and_readers() {
+        let map = Arc::new(SyncSkipListMap::new());
+        let mut threads = Vec::new();
+        for t in 0..4 {
+            let map = Arc::clone(&map);
+            threads.push(thread::spawn(move || {
+                for i in 0..500 {
+                    map.insert(i * 4 + t, t);
+                }
+                map.insert_all((0..100).map(|i| (10_000 + i * 4 + t, t)));
+            }));
+        }
+        for _ in 0..2 {
+            let map = Arc::clone(&map);
+            threads.push(thread::spawn(move || {
+                for _ in 0..200 {
+                    // whatever the writers have done so far, the map is always in key order
+                    let snapshot = map.to_vec();
+                    assert!(snapshot.windows(2).all(|pair| pair[0].0 < pair[1].0));
+                }
+            }));
+        }
+        for handle in threads {
+            handle.join().unwrap();
+        }
+
+        let map = Arc::try_unwrap(map).unwrap().into_inner();
+        assert_eq!(map.size(), 2400);
+        assert!(map.keys().take(2000).copied().eq(0..2000));
+        assert_eq!(map.get(&10_001), Some(&1));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod queue;
+pub mod stack;
+
+pub use self::queue::Queue;
+pub use self::stack::Stack;
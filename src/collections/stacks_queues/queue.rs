@@ -0,0 +1,159 @@
+use crate::collections::lists::{List, ListIntoIterator, ListIterator};
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+
+// first in first out on top of List, elements join at the tail and leave from the head so every operation is O(1)
+pub struct Queue<T> {
+    list: List<T>
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Self {
+            list: List::new()
+        }
+    }
+
+    pub fn push(&mut self, element: T) -> &mut Self {
+        self.list.append(element);
+        self
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    // the element pop hands out next
+    pub fn peek(&self) -> Option<&T> {
+        self.list.first()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.get_mut(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.list.clear();
+        self
+    }
+
+    // front to back
+    pub fn iter(&self) -> ListIterator<'_, T> {
+        self.list.iter()
+    }
+
+    // takes every element out at once and yields them front to back, the queue is empty afterwards
+    pub fn drain(&mut self) -> ListIntoIterator<T> {
+        mem::take(&mut self.list).into_iter()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+// pops every element, front to back
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = ListIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = ListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> Clone for Queue<T> {
+    fn clone(&self) -> Self {
+        Queue {
+            list: self.list.clone()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Queue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<T: Eq> Eq for Queue<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Queue;
+
+    #[test]
+    fn queue_push_pop() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.pop(), None);
+        queue.push(1).push(2).push(3);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some(1));
+        queue.push(4);
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+        assert!(queue.is_empty());
+
+        // the tail is reset once the queue runs empty
+        queue.push(5);
+        assert_eq!(queue.peek(), Some(&5));
+    }
+
+    #[test]
+    fn queue_peek() {
+        let mut queue: Queue<String> = vec![String::from("a"), String::from("b")].into_iter().collect();
+        queue.peek_mut().unwrap().push('!');
+        assert_eq!(queue.peek().map(String::as_str), Some("a!"));
+        assert_eq!(format!("{:?}", queue), r#"["a!", "b"]"#);
+    }
+
+    #[test]
+    fn queue_drain() {
+        let mut queue: Queue<i32> = (0..4).collect();
+        assert_eq!(queue.drain().collect::<Vec<i32>>(), vec![0, 1, 2, 3]);
+        assert!(queue.is_empty());
+        queue.push(5);
+        assert_eq!(queue.into_iter().collect::<Vec<i32>>(), vec![5]);
+    }
+}
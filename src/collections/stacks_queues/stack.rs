@@ -0,0 +1,158 @@
+use crate::collections::lists::{List, ListIntoIterator, ListIterator};
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+
+// last in first out on top of List, the top of the stack is the head of the list so every operation is O(1)
+pub struct Stack<T> {
+    list: List<T>
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self {
+            list: List::new()
+        }
+    }
+
+    pub fn push(&mut self, element: T) -> &mut Self {
+        self.list.prepend(element);
+        self
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.list.first()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.get_mut(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.list.clear();
+        self
+    }
+
+    // top to bottom
+    pub fn iter(&self) -> ListIterator<'_, T> {
+        self.list.iter()
+    }
+
+    // takes every element out at once and yields them top to bottom, the stack is empty afterwards
+    pub fn drain(&mut self) -> ListIntoIterator<T> {
+        mem::take(&mut self.list).into_iter()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the last element of the iterator ends up on top
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+// pops every element, top to bottom
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = ListIntoIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = ListIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> Clone for Stack<T> {
+    fn clone(&self) -> Self {
+        Stack {
+            list: self.list.clone()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Stack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<T: Eq> Eq for Stack<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+
+    #[test]
+    fn stack_push_pop() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.pop(), None);
+        stack.push(1).push(2).push(3);
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn stack_peek() {
+        let mut stack: Stack<i32> = vec![1, 2].into_iter().collect();
+        assert_eq!(stack.peek(), Some(&2));
+        *stack.peek_mut().unwrap() = 20;
+        assert_eq!(stack.pop(), Some(20));
+        assert_eq!(format!("{:?}", stack), "[1]");
+    }
+
+    #[test]
+    fn stack_drain() {
+        let mut stack: Stack<i32> = (0..4).collect();
+        assert_eq!(stack.drain().collect::<Vec<i32>>(), vec![3, 2, 1, 0]);
+        assert!(stack.is_empty());
+        stack.push(5);
+        assert_eq!(stack.into_iter().collect::<Vec<i32>>(), vec![5]);
+    }
+}
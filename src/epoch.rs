@@ -0,0 +1,172 @@
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+// epoch based memory reclamation for the lock free collections.
+//
+// a thread pins the current epoch before it reads any shared node and unpins when it is done. unlinked nodes are
+// retired into the bag of the epoch they were retired in instead of being freed straight away. the global epoch
+// only moves on from e to e + 1 once nobody is pinned in e - 1, so pinned threads are always in the current or the
+// previous epoch, and a node retired in e - 2 can no longer be reached by anyone. that bag is freed while moving to
+// e + 1, right before it is reused. every atomic is SeqCst, which keeps the reasoning above simple.
+pub(crate) struct Collector<T> {
+    epoch: AtomicUsize,
+    pins: [AtomicUsize; 3], // amount of threads pinned in each epoch modulo 3
+    bags: [AtomicPtr<Retired<T>>; 3] // lock free stacks of the nodes retired in each epoch modulo 3
+}
+
+struct Retired<T> {
+    node: *mut T,
+    next: *mut Retired<T>
+}
+
+// keeps the epoch it was created in pinned until it is dropped
+pub(crate) struct Guard<'a, T> {
+    collector: &'a Collector<T>,
+    epoch: usize
+}
+
+impl<T> Collector<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            epoch: AtomicUsize::new(0),
+            pins: [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)],
+            bags: [AtomicPtr::new(ptr::null_mut()), AtomicPtr::new(ptr::null_mut()),
+                   AtomicPtr::new(ptr::null_mut())]
+        }
+    }
+
+    pub(crate) fn pin(&self) -> Guard<'_, T> {
+        loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            self.pins[epoch % 3].fetch_add(1, Ordering::SeqCst);
+
+            // the epoch may have moved on before the pin was counted, in that case the pin does not protect
+            // anything and has to be taken again in the new epoch
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                return Guard { collector: self, epoch };
+            }
+            self.pins[epoch % 3].fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    // hands a node over to the collector, it is freed once no pinned thread can still be looking at it.
+    //
+    // SAFETY: node must come from Box::into_raw, must already be unreachable for threads that pin from now on and
+    // must only be retired once. the caller has to be pinned.
+    pub(crate) unsafe fn retire(&self, node: *mut T) {
+        let retired = Box::into_raw(Box::new(Retired { node, next: ptr::null_mut() }));
+        let bag = &self.bags[self.epoch.load(Ordering::SeqCst) % 3];
+        loop {
+            let head = bag.load(Ordering::SeqCst);
+            (*retired).next = head;
+            if bag.compare_exchange(head, retired, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                break;
+            }
+        }
+
+        self.try_advance();
+    }
+
+    fn try_advance(&self) {
+        let epoch = self.epoch.load(Ordering::SeqCst);
+
+        // somebody is still pinned in the previous epoch
+        if self.pins[(epoch + 2) % 3].load(Ordering::SeqCst) != 0 {
+            return;
+        }
+
+        if self.epoch.compare_exchange(epoch, epoch + 1, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            // the bag of epoch - 2 becomes the bag of epoch + 1, free what it still holds
+            let bag = self.bags[(epoch + 1) % 3].swap(ptr::null_mut(), Ordering::SeqCst);
+            unsafe { Collector::free(bag) };
+        }
+    }
+
+    // SAFETY: nobody may be able to reach the retired nodes anymore
+    unsafe fn free(mut retired: *mut Retired<T>) {
+        while !retired.is_null() {
+            let entry = Box::from_raw(retired);
+            drop(Box::from_raw(entry.node));
+            retired = entry.next;
+        }
+    }
+}
+
+impl<T> Drop for Collector<T> {
+    fn drop(&mut self) {
+        // nobody can be pinned anymore, everything left over goes
+        for bag in self.bags.iter() {
+            unsafe { Collector::free(bag.swap(ptr::null_mut(), Ordering::SeqCst)) };
+        }
+    }
+}
+
+impl<'a, T> Drop for Guard<'a, T> {
+    fn drop(&mut self) {
+        self.collector.pins[self.epoch % 3].fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Collector;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn epoch_pin_and_unpin() {
+        let collector = Collector::new() as Collector<u32>;
+        let guard = collector.pin();
+        assert_eq!(collector.pins[guard.epoch % 3].load(Ordering::SeqCst), 1);
+        drop(guard);
+        assert_eq!(collector.pins.iter().map(|p| p.load(Ordering::SeqCst)).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn epoch_pinned_thread_blocks_reclamation() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let collector = Collector::new();
+
+        let reader = collector.pin();
+        {
+            let _writer = collector.pin();
+            unsafe { collector.retire(Box::into_raw(Box::new(Tracked(Arc::clone(&dropped))))) };
+        }
+
+        // the reader pinned before the node was retired, so the node must survive however often we try
+        for _ in 0..10 {
+            let _guard = collector.pin();
+            collector.try_advance();
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 0);
+
+        // once the reader is gone the node is freed after the epoch moved on twice
+        drop(reader);
+        for _ in 0..3 {
+            collector.try_advance();
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn epoch_drop_frees_everything() {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let collector = Collector::new();
+        let guard = collector.pin();
+        for _ in 0..5 {
+            unsafe { collector.retire(Box::into_raw(Box::new(Tracked(Arc::clone(&dropped))))) };
+        }
+        drop(guard);
+        drop(collector);
+        assert_eq!(dropped.load(Ordering::SeqCst), 5);
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+// errors reported by the fallible (try_*) operations of the collections
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Error {
+    // the index is not a valid position, len is the size of the collection at the time
+    IndexOutOfBounds { index: usize, len: usize },
+
+    // the element searched for is not in the collection
+    NotFound,
+
+    // linking the nodes would create a reference cycle
+    Cycle,
+
+    // decrease_key was handed a key greater than the one it should replace
+    KeyIncreased,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::IndexOutOfBounds { index, len } => {
+                write!(f, "index out of bounds: the len is {} but the index is {}", len, index)
+            },
+            Error::NotFound => write!(f, "element not found"),
+            Error::Cycle => write!(f, "link would create a cycle"),
+            Error::KeyIncreased => write!(f, "new key is greater than the current key"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn error_display() {
+        assert_eq!(Error::IndexOutOfBounds { index: 4, len: 2 }.to_string(),
+                   "index out of bounds: the len is 2 but the index is 4");
+        assert_eq!(Error::NotFound.to_string(), "element not found");
+        assert_eq!(Error::Cycle.to_string(), "link would create a cycle");
+        assert_eq!(Error::KeyIncreased.to_string(), "new key is greater than the current key");
+    }
+
+    #[test]
+    fn error_is_std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(Error::NotFound);
+        assert_eq!(err.to_string(), "element not found");
+    }
+}
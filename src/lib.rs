@@ -1,5 +1,8 @@
+pub mod error;
+pub(crate) mod epoch;
 pub mod node;
 pub mod collections;
+pub mod prelude;
 
 
 #[cfg(test)]
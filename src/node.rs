@@ -1,9 +1,13 @@
+use crate::error::{Error, Result};
 use std::cell::{RefCell};
-use std::rc::Rc;
+use std::ptr;
+use std::rc::{Rc, Weak};
 
 // RC allows multiple ownership, RefCell allows interior mutability.
 pub type NodePtr<T> = Rc<RefCell<Node<T>>>;
 pub type NodePtrOpt<T> = Option<NodePtr<T>>;
+// non owning link, for back pointers and anything else that must not keep a node alive or close a cycle
+pub type WeakNodePtr<T> = Weak<RefCell<Node<T>>>;
 
 
 #[derive(PartialEq, Debug)]
@@ -26,24 +30,128 @@ impl<T> Node<T> {
     }
 
     pub fn get_next(&self) -> NodePtrOpt<T> {
-        match &self.next {
-            Some(next) => Some(next.clone()),
-            None => None
-        }
+        self.next.clone()
     }
 
+    // unchecked, linking back to an earlier node creates a cycle that Rc never frees, see try_set_next_node
     pub fn set_next_node(&mut self, node: NodePtrOpt<T>) {
         self.next = node;
     }
+
+    // like set_next_node, but refuses when node leads back to this node or into a cycle of its own.
+    // walks everything reachable from node once, so it is O(n) in the length of that chain.
+    pub fn try_set_next_node(&mut self, node: NodePtrOpt<T>) -> Result<()> {
+        if let Some(start) = &node {
+            let this = self as *const Node<T>;
+
+            // brent's algorithm, the tortoise jumps to the hare whenever the hare did a power of two steps
+            let mut tortoise = Rc::clone(start);
+            let mut hare = Rc::clone(start);
+            let (mut power, mut steps) = (1, 0);
+            loop {
+                // compared before borrowing, this node is already borrowed mutably by our caller
+                if ptr::eq(hare.as_ptr(), this) {
+                    return Err(Error::Cycle);
+                }
+
+                hare = match Node::next_of(&hare) {
+                    Some(next) => next,
+                    None => break,
+                };
+
+                steps += 1;
+                if Rc::ptr_eq(&hare, &tortoise) {
+                    return Err(Error::Cycle);
+                }
+                if steps == power {
+                    tortoise = Rc::clone(&hare);
+                    power *= 2;
+                    steps = 0;
+                }
+            }
+        }
+
+        self.next = node;
+        Ok(())
+    }
+
+    // floyd's algorithm, true when following the links from head never reaches the end
+    pub fn has_cycle(head: &NodePtr<T>) -> bool {
+        Node::meeting_point(head).is_some()
+    }
+
+    // the first node of the cycle reachable from head, none when the chain ends
+    pub fn cycle_start(head: &NodePtr<T>) -> NodePtrOpt<T> {
+        // the distance from head to the start equals the distance from the meeting point to the start
+        let mut a = Node::meeting_point(head)?;
+        let mut b = Rc::clone(head);
+        while !Rc::ptr_eq(&a, &b) {
+            a = Node::next_of(&a)?;
+            b = Node::next_of(&b)?;
+        }
+        Some(a)
+    }
+
+    // brent's algorithm, the amount of nodes in the cycle reachable from head, none when the chain ends
+    pub fn cycle_len(head: &NodePtr<T>) -> Option<usize> {
+        let mut tortoise = Rc::clone(head);
+        let mut hare = head.borrow().get_next()?;
+        let (mut power, mut len) = (1, 1);
+        while !Rc::ptr_eq(&tortoise, &hare) {
+            if power == len {
+                tortoise = Rc::clone(&hare);
+                power *= 2;
+                len = 0;
+            }
+            hare = Node::next_of(&hare)?;
+            len += 1;
+        }
+        Some(len)
+    }
+
+    fn next_of(node: &NodePtr<T>) -> NodePtrOpt<T> {
+        node.borrow().get_next()
+    }
+
+    // the node where the slow and the fast pointer of floyd's algorithm meet
+    fn meeting_point(head: &NodePtr<T>) -> NodePtrOpt<T> {
+        let mut slow = Rc::clone(head);
+        let mut fast = Rc::clone(head);
+        loop {
+            let next = Node::next_of(&fast)?;
+            fast = Node::next_of(&next)?;
+            slow = Node::next_of(&slow)?;
+            if Rc::ptr_eq(&slow, &fast) {
+                return Some(slow);
+            }
+        }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use super::Node;
+    use super::{Node, NodePtr, WeakNodePtr};
+    use crate::error::Error;
     use std::rc::Rc;
     use std::cell::RefCell;
 
+    // builds a chain of len nodes and returns them in order
+    fn chain(len: usize) -> Vec<NodePtr<usize>> {
+        let nodes: Vec<NodePtr<usize>> = (0..len).map(Node::new).collect();
+        for pair in nodes.windows(2) {
+            pair[0].borrow_mut().set_next_node(Some(Rc::clone(&pair[1])));
+        }
+        nodes
+    }
+
+    // the tests close cycles by hand, open them again so the nodes are freed
+    fn break_cycle(nodes: &[NodePtr<usize>]) {
+        for node in nodes {
+            node.borrow_mut().set_next_node(None);
+        }
+    }
+
     #[test]
     fn node_new() {
         let n = Node::new(10);
@@ -85,4 +193,60 @@ mod tests {
         let t = n.as_ref().borrow_mut().element;
         assert_eq!(t, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn node_no_cycle() {
+        let nodes = chain(5);
+        assert!(!Node::has_cycle(&nodes[0]));
+        assert!(Node::cycle_start(&nodes[0]).is_none());
+        assert_eq!(Node::cycle_len(&nodes[0]), None);
+        assert_eq!(Node::cycle_len(&Node::new(1)), None);
+    }
+
+    #[test]
+    fn node_cycle_detection() {
+        for (len, start) in [(1, 0), (2, 0), (5, 2), (8, 7), (100, 37)] {
+            let nodes = chain(len);
+            nodes[len - 1].borrow_mut().set_next_node(Some(Rc::clone(&nodes[start])));
+
+            assert!(Node::has_cycle(&nodes[0]));
+            assert!(Rc::ptr_eq(&Node::cycle_start(&nodes[0]).unwrap(), &nodes[start]));
+            assert_eq!(Node::cycle_len(&nodes[0]), Some(len - start));
+            break_cycle(&nodes);
+        }
+    }
+
+    #[test]
+    fn node_try_set_next_node() {
+        let nodes = chain(4);
+        assert_eq!(nodes[3].borrow_mut().try_set_next_node(Some(Rc::clone(&nodes[0]))), Err(Error::Cycle));
+        assert_eq!(nodes[3].borrow_mut().try_set_next_node(Some(Rc::clone(&nodes[3]))), Err(Error::Cycle));
+        assert!(nodes[3].borrow().get_next().is_none());
+
+        let extra = Node::new(4);
+        assert_eq!(nodes[3].borrow_mut().try_set_next_node(Some(Rc::clone(&extra))), Ok(()));
+        assert_eq!(nodes[3].borrow_mut().try_set_next_node(None), Ok(()));
+        assert!(!Node::has_cycle(&nodes[0]));
+    }
+
+    #[test]
+    fn node_try_set_next_node_into_existing_cycle() {
+        let nodes = chain(3);
+        nodes[2].borrow_mut().set_next_node(Some(Rc::clone(&nodes[1])));
+        let outside = Node::new(9);
+        assert_eq!(outside.borrow_mut().try_set_next_node(Some(Rc::clone(&nodes[0]))), Err(Error::Cycle));
+        break_cycle(&nodes);
+    }
+
+    #[test]
+    fn node_weak_link() {
+        let a = Node::new(1);
+        let b = Node::new(2);
+        a.borrow_mut().set_next_node(Some(Rc::clone(&b)));
+        let back: WeakNodePtr<i32> = Rc::downgrade(&a);
+        assert_eq!(back.upgrade().unwrap().borrow().element, 1);
+        drop(a);
+        assert!(back.upgrade().is_none());
+        assert_eq!(Rc::strong_count(&b), 1);
+    }
+}
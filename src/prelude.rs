@@ -0,0 +1,8 @@
+// re-exports every collection so users can `use rads::prelude::*;`
+pub use crate::collections::deques::{Overflow, RingDeque};
+pub use crate::collections::heaps::{BinaryHeap, BinomialHandle, BinomialHeap, FibonacciHandle, FibonacciHeap,
+                                    IndexedPriorityQueue, MergeableHeap, PairingHandle, PairingHeap, QueueHandle};
+pub use crate::collections::lists::{ArenaList, CircularList, DoublyLinkedList, List, LockFreeList, PersistentList,
+                                    SyncList, UnrolledList};
+pub use crate::collections::skiplists::{SkipListMap, SkipListSet, SyncSkipListMap};
+pub use crate::collections::stacks_queues::{Queue, Stack};
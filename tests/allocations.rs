@@ -0,0 +1,64 @@
+// counts heap allocations made by the list backends, this file holds a single test so no other test allocates
+// on another thread while the counter is being read
+use rads::collections::lists::{ArenaList, List};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn arena_list_allocates_less_than_list() {
+    const N: u32 = 100_000;
+
+    let list_allocations = allocations_during(|| {
+        let mut list = List::new() as List<u32>;
+        for x in 0..N {
+            list.append(x);
+        }
+        assert_eq!(list.size(), N as usize);
+    });
+
+    let arena_allocations = allocations_during(|| {
+        let mut list = ArenaList::new() as ArenaList<u32>;
+        for x in 0..N {
+            list.append(x);
+        }
+        assert_eq!(list.size(), N as usize);
+    });
+
+    let preallocated_allocations = allocations_during(|| {
+        let mut list = ArenaList::with_capacity(N as usize) as ArenaList<u32>;
+        for x in 0..N {
+            list.append(x);
+        }
+        list.remove_at(0).append(N);
+        assert_eq!(list.size(), N as usize);
+    });
+
+    // one allocation per node against a slab that only grows geometrically
+    assert!(list_allocations >= N as usize);
+    assert!(arena_allocations < 64);
+    assert_eq!(preallocated_allocations, 1);
+}
@@ -0,0 +1,70 @@
+// integration tests, these only use the public api of the crate
+use rads::collections::lists::List;
+use rads::error::Error;
+
+#[test]
+fn list_public_path() {
+    let mut list = List::new() as List<i32>;
+    list.append(2).prepend(1).append(3);
+    assert_eq!(list.size(), 3);
+    assert_eq!(list, List::from(vec![1, 2, 3]));
+}
+
+#[test]
+fn list_prelude() {
+    use rads::prelude::*;
+
+    let mut list: List<i32> = List::default();
+    assert!(list.is_empty());
+    list.append(1);
+    assert!(list.has(&1));
+}
+
+#[test]
+fn list_collections_reexport() {
+    let list = rads::collections::List::from(vec![1, 2, 3]);
+    assert_eq!(list.iter().copied().collect::<Vec<i32>>(), vec![1, 2, 3]);
+}
+
+#[test]
+fn list_editing() {
+    let mut list = List::from(vec![1, 2, 4]);
+    list.insert(2, 3).remove(&1).reverse();
+    assert_eq!(list, List::from(vec![4, 3, 2]));
+    assert_eq!(list.index(&2), Some(2));
+    list.remove_at(0).clear();
+    assert!(list.is_empty());
+}
+
+#[test]
+fn list_fallible_editing() {
+    let mut list = List::from(vec![1, 2]);
+    assert_eq!(list.try_insert(3, 3).unwrap_err(), Error::IndexOutOfBounds { index: 3, len: 2 });
+    assert_eq!(list.try_remove_at(2).unwrap_err(), Error::IndexOutOfBounds { index: 2, len: 2 });
+    assert_eq!(list.try_remove(&5).unwrap_err(), Error::NotFound);
+    list.try_insert(2, 3).unwrap().try_remove(&1).unwrap();
+    assert_eq!(list, List::from(vec![2, 3]));
+}
+
+#[test]
+fn doubly_linked_list_swaps_with_list() {
+    use rads::collections::lists::DoublyLinkedList;
+
+    let mut list = DoublyLinkedList::from(vec![3, 1, 2]);
+    list.append(0).sort().insert(1, 5).remove(&2);
+    assert_eq!(list, DoublyLinkedList::from(vec![0, 5, 1, 3]));
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.iter().rev().copied().collect::<Vec<i32>>(), vec![1, 5, 0]);
+}
+
+#[test]
+fn list_iterator_pipelines() {
+    let list: List<i32> = (1..=5).collect();
+    let evens: List<i32> = list.iter().filter(|x| *x % 2 == 0).copied().collect();
+    assert_eq!(evens, List::from([2, 4]));
+
+    let mut words = List::from([String::from("b"), String::from("a")]);
+    words.extend(vec![String::from("c")]);
+    words.sort();
+    assert_eq!(words.into_iter().collect::<Vec<String>>().concat(), "abc");
+}